@@ -0,0 +1,156 @@
+//! Lint corpora against the criterion functions which read them, without compiling the crate
+
+use crate::listdir::{case_chain, list_case_entries, list_dir, resolve_case_file};
+use crate::meta::Meta;
+use crate::scan::{same_path, scan_crate, Usage};
+use std::collections::BTreeSet;
+use std::io::Result;
+use std::path::{Path, PathBuf};
+
+/// A problem with a corpus or case directory
+#[derive(Debug, PartialEq, Eq, derive_more::Display)]
+#[display(fmt = "{}: {}", "path.display()", message)]
+pub struct Finding {
+    /// The corpus or case directory with the problem
+    pub path: PathBuf,
+    /// A description of the problem
+    pub message: String,
+}
+
+/// The result of checking a crate
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The number of criterion functions found
+    pub criteria: usize,
+    /// The number of distinct corpora checked
+    pub corpora: usize,
+    /// Every problem found, grouped by corpus
+    pub findings: Vec<Finding>,
+}
+
+/// Check every corpus used by the criterion functions of the crate at `crate_dir`
+///
+/// Each case directory is checked for argument files which are missing, entries which no criterion
+/// reads, names which do not produce valid test identifiers, invalid metadata files, and invalid
+/// base cases. Each corpus is checked against the case count requirements of its criteria. Every
+/// directory in `expected_corpora` which no criterion uses is also reported.
+///
+/// Unused corpora are only found among `expected_corpora`, since any directory might be one. Test
+/// names are checked without the suffixes added by `types(...)` and `params(...)`, which are
+/// always valid identifier characters, so collisions between suffixed names are only reported by
+/// the macro.
+pub fn check_crate(crate_dir: &Path, expected_corpora: &[PathBuf]) -> Result<Report> {
+    let usages = scan_crate(crate_dir)?;
+
+    // Group usages by corpus in order of first appearance:
+    let mut corpora: Vec<(&Path, Vec<&Usage>)> = vec![];
+    for usage in usages.iter() {
+        match corpora
+            .iter_mut()
+            .find(|(dir, _)| same_path(dir, &usage.params.dir))
+        {
+            Some((_, group)) => group.push(usage),
+            None => corpora.push((&usage.params.dir, vec![usage])),
+        }
+    }
+
+    let mut findings = vec![];
+    for (dir, group) in corpora.iter() {
+        check_corpus(dir, group, &mut findings);
+    }
+    for dir in expected_corpora {
+        if !corpora.iter().any(|(used, _)| same_path(used, dir)) {
+            findings.push(Finding {
+                path: dir.clone(),
+                message: "corpus is not used by any criterion function".to_string(),
+            });
+        }
+    }
+
+    Ok(Report {
+        criteria: usages.len(),
+        corpora: corpora.len(),
+        findings,
+    })
+}
+
+fn check_corpus(dir: &Path, usages: &[&Usage], findings: &mut Vec<Finding>) {
+    let mut report = |path: &Path, message: String| {
+        findings.push(Finding {
+            path: path.to_path_buf(),
+            message,
+        })
+    };
+
+    let cases = match list_dir(dir, usages[0].params.sort) {
+        Ok(cases) => cases,
+        Err(e) => return report(dir, format!("cannot list cases: {}", e)),
+    };
+
+    for usage in usages {
+        if let Err(e) = usage.params.check_case_count(cases.len()) {
+            report(dir, format!("{} (`{}`)", e, usage.function));
+        }
+    }
+
+    let fileargs: BTreeSet<&str> = usages.iter().flat_map(|u| u.file_args()).collect();
+    for case in cases.iter() {
+        let casedir = dir.join(&case.dirname);
+
+        for usage in usages {
+            let testname = format!("{}_{}", usage.function, case.name);
+            if syn::parse_str::<syn::Ident>(&testname).is_err() {
+                report(
+                    &casedir,
+                    format!("does not produce a valid test name: {}", testname),
+                );
+            }
+        }
+
+        if let Err(e) = case_chain(&casedir) {
+            report(&casedir, e.to_string());
+        }
+        for arg in fileargs.iter() {
+            // An invalid inheritance chain is reported once above:
+            if let Ok(None) = resolve_case_file(dir, &casedir, arg) {
+                let readers: Vec<&str> = usages
+                    .iter()
+                    .filter(|u| u.file_args().any(|a| a == *arg))
+                    .map(|u| u.function.as_str())
+                    .collect();
+                report(
+                    &casedir,
+                    format!("missing file `{}` read by `{}`", arg, readers.join("`, `")),
+                );
+            }
+        }
+
+        if let Err(e) = Meta::read(&casedir) {
+            report(&casedir, e);
+        }
+
+        match list_case_entries(&casedir) {
+            Ok(entries) => {
+                let unused: Vec<String> = entries
+                    .into_iter()
+                    .filter(|name| {
+                        !fileargs.contains(name.as_str())
+                            && !usages
+                                .iter()
+                                .any(|u| u.copies_case || u.params.is_ignored(name))
+                    })
+                    .collect();
+                if !unused.is_empty() {
+                    report(
+                        &casedir,
+                        format!("entries not used by any argument: {}", unused.join(", ")),
+                    );
+                }
+            }
+            Err(e) => report(&casedir, format!("cannot list entries: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
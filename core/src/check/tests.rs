@@ -0,0 +1,110 @@
+use crate::check::{check_crate, Finding};
+use std::io::Result;
+use std::path::{Path, PathBuf};
+use target_test_dir::test_with_dir;
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, contents)
+}
+
+#[test_with_dir]
+fn check_crate_reports_problems(testdir: PathBuf) -> Result<()> {
+    write(
+        &testdir.join("tests/vectors.rs"),
+        r#"
+        #[test_vectors(dir = "vectors")]
+        fn parse(input: &[u8], expected: &[u8]) {}
+
+        #[test_vectors(dir = "empty")]
+        fn nothing(input: &[u8]) {}
+        "#,
+    )?;
+    write(&testdir.join("vectors/good/input"), "")?;
+    write(&testdir.join("vectors/good/expected"), "")?;
+    write(&testdir.join("vectors/good/README.md"), "")?;
+    write(&testdir.join("vectors/incomplete/input"), "")?;
+    write(&testdir.join("vectors/typo/input"), "")?;
+    write(&testdir.join("vectors/typo/expected"), "")?;
+    write(&testdir.join("vectors/typo/expectd"), "")?;
+    write(&testdir.join("vectors/bad-name/input"), "")?;
+    write(&testdir.join("vectors/bad-name/expected"), "")?;
+    write(&testdir.join("vectors/bad_meta/input"), "")?;
+    write(&testdir.join("vectors/bad_meta/expected"), "")?;
+    write(
+        &testdir.join("vectors/bad_meta/meta.json"),
+        "{\"tags\": \"fast\"}",
+    )?;
+    std::fs::create_dir_all(testdir.join("empty"))?;
+    std::fs::create_dir_all(testdir.join("orphan"))?;
+
+    let report = check_crate(&testdir, &[testdir.join("vectors"), testdir.join("orphan")])?;
+    assert_eq!(report.criteria, 2);
+    assert_eq!(report.corpora, 2);
+
+    let vectors = testdir.join("vectors");
+    let finding = |path: PathBuf, message: &str| Finding {
+        path,
+        message: message.to_string(),
+    };
+    assert_eq!(
+        report.findings,
+        [
+            finding(
+                vectors.join("bad-name"),
+                "does not produce a valid test name: parse_bad-name"
+            ),
+            finding(
+                vectors.join("bad_meta"),
+                &format!(
+                    "{}: invalid type: string \"fast\", expected a sequence at line 1 column 15",
+                    vectors.join("bad_meta/meta.json").display(),
+                ),
+            ),
+            finding(
+                vectors.join("incomplete"),
+                "missing file `expected` read by `parse`"
+            ),
+            finding(
+                vectors.join("typo"),
+                "entries not used by any argument: expectd"
+            ),
+            finding(
+                testdir.join("empty"),
+                &format!(
+                    "corpus directory {:?} contains no case directories; use `allow_empty = true` if this is intended (`nothing`)",
+                    testdir.join("empty"),
+                ),
+            ),
+            finding(
+                testdir.join("orphan"),
+                "corpus is not used by any criterion function"
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[test_with_dir]
+fn case_temp_dir_uses_every_entry(testdir: PathBuf) -> Result<()> {
+    write(
+        &testdir.join("tests/workdir.rs"),
+        r#"
+        #[test_vectors(dir = "vectors")]
+        fn build(workdir: test_vectors::CaseTempDir, expected: &[u8]) {}
+        "#,
+    )?;
+    write(&testdir.join("vectors/alpha/expected"), "")?;
+    write(&testdir.join("vectors/alpha/Makefile"), "")?;
+    write(&testdir.join("vectors/beta/Makefile"), "")?;
+
+    let report = check_crate(&testdir, &[])?;
+    assert_eq!(
+        report.findings,
+        [Finding {
+            path: testdir.join("vectors/beta"),
+            message: "missing file `expected` read by `build`".to_string(),
+        }]
+    );
+    Ok(())
+}
@@ -0,0 +1,21 @@
+/// An error parsing criterion function attributes or signatures
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum Error {
+    Syn(syn::Error),
+    Darling(darling::Error),
+}
+
+/// A [Result](std::result::Result) with [Error]
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Convert into tokens which report this error at compile time
+    pub fn into_compile_error(self) -> proc_macro2::TokenStream {
+        use Error::*;
+
+        match self {
+            Syn(e) => e.into_compile_error(),
+            Darling(e) => e.write_errors(),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use quote::ToTokens;
+use syn::Type;
+
+/// Parse the argument names and types of a criterion function signature
+pub fn parse_fn_args(sig: &syn::Signature) -> Result<(Vec<String>, Vec<Type>), String> {
+    if let Some(receiver) = sig.receiver() {
+        return Err(format!(
+            "test functions may not take receiver: {}",
+            receiver.into_token_stream(),
+        ));
+    }
+
+    let zipped = sig
+        .inputs
+        .iter()
+        .map(parse_fn_arg_name)
+        .collect::<Result<Vec<(String, Type)>, _>>()?;
+
+    Ok(zipped.into_iter().unzip())
+}
+
+/// Whether an argument of type `ty` takes a `CaseTempDir` copy of the case directory rather than
+/// the contents of a case file
+///
+/// Types are matched by name, since the macro cannot resolve paths.
+pub fn is_case_temp_dir(ty: &Type) -> bool {
+    is_named(ty, "CaseTempDir")
+}
+
+/// Whether an argument of type `ty` is a `Tree`, which takes the files beneath a case
+/// subdirectory rather than the contents of a file
+///
+/// Types are matched by name like [is_case_temp_dir], so only a `Tree` itself is recognized, not
+/// other types converting from one.
+pub fn is_tree(ty: &Type) -> bool {
+    is_named(ty, "Tree")
+}
+
+fn is_named(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name),
+        _ => false,
+    }
+}
+
+fn parse_fn_arg_name(fnarg: &syn::FnArg) -> Result<(String, Type), String> {
+    let syn::PatType { pat, ty, .. } = match fnarg {
+        syn::FnArg::Typed(pt) => pt,
+        _ => unreachable!("receiver check post-condition failure"),
+    };
+
+    match &**pat {
+        syn::Pat::Ident(syn::PatIdent { ident, .. }) => Ok((ident.to_string(), (**ty).clone())),
+        other => Err(format!(
+            "expected arg identifier, found: {}",
+            other.into_token_stream()
+        )),
+    }
+}
@@ -0,0 +1,28 @@
+//! Corpus discovery shared by the [test-vectors](https://docs.rs/test-vectors) crate and its proc
+//! macro
+//!
+//! Both compile-time case generation and the runtime corpus API use this crate, so they always
+//! agree on which directories are cases, how cases are named, and in what order they are listed.
+//!
+//! With the `parse` cargo feature, this crate also parses `test_vectors` attribute arguments,
+//! criterion function signatures, and case metadata files, so command line tools interpret source
+//! code exactly as the macro does.
+//!
+//! With the `manifest` cargo feature, it verifies and generates checksum manifests of corpus files.
+
+#[cfg(feature = "parse")]
+pub mod check;
+#[cfg(feature = "parse")]
+pub mod error;
+#[cfg(feature = "parse")]
+pub mod fnargs;
+pub mod listdir;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+#[cfg(feature = "parse")]
+pub mod meta;
+#[cfg(feature = "parse")]
+pub mod params;
+#[cfg(feature = "parse")]
+pub mod scan;
+pub mod tags;
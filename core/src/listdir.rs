@@ -0,0 +1,328 @@
+use std::cmp::Ordering;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// The corpus subdirectory holding files shared by every case, which is not itself a case
+pub const SHARED_DIR: &str = "_shared";
+
+/// The marker file naming the base case a case inherits missing files from; see [case_chain]
+pub const EXTENDS_FILE: &str = "extends.toml";
+
+/// The order in which cases are listed
+///
+/// Cases are always sorted by name so that results, such as generated code, do not depend on
+/// filesystem `read_dir` ordering.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Sort by the byte order of case names
+    #[default]
+    Lexical,
+    /// Sort runs of ASCII digits by numeric value, so `case-2` precedes `case-10`
+    Natural,
+}
+
+impl SortOrder {
+    /// Compare two case names in this order
+    pub fn compare(self, a: &str, b: &str) -> Ordering {
+        match self {
+            SortOrder::Lexical => a.cmp(b),
+            SortOrder::Natural => natural_cmp(a, b).then_with(|| a.cmp(b)),
+        }
+    }
+}
+
+impl std::str::FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "lexical" => Ok(SortOrder::Lexical),
+            "natural" => Ok(SortOrder::Natural),
+            other => Err(format!("unknown sort order: {:?}", other)),
+        }
+    }
+}
+
+/// A case directory within a corpus
+#[derive(Debug)]
+pub struct CaseDir {
+    /// The case name used to derive test identifiers
+    pub name: String,
+    /// The directory name on disk, which differs from `name` when it is not valid UTF-8
+    pub dirname: OsString,
+}
+
+impl CaseDir {
+    fn new(dirname: OsString) -> Self {
+        let name = match dirname.to_str() {
+            Some(s) => s.to_string(),
+            None => escape_non_utf8(dirname.as_encoded_bytes()),
+        };
+        CaseDir { name, dirname }
+    }
+
+    /// Whether the directory name is valid UTF-8 and thus usable in a string literal path
+    pub fn is_utf8(&self) -> bool {
+        self.dirname.to_str().is_some()
+    }
+}
+
+/// List the case directories of the corpus directory `dir`
+///
+/// Every directory inside `dir`, after traversing symlinks, is a case directory except
+/// [SHARED_DIR], which is skipped even if it was meant as a case, and other entries are skipped.
+/// The result is sorted by case name in `order`, and it is an error for two directories to map to
+/// the same case name.
+pub fn list_dir(dir: &Path, order: SortOrder) -> std::io::Result<Vec<CaseDir>> {
+    let mut cases = vec![];
+    for entres in dir.read_dir()? {
+        let entry = entres?;
+        if entry.path().metadata()?.is_dir() && entry.file_name() != SHARED_DIR {
+            cases.push(CaseDir::new(entry.file_name()));
+        }
+    }
+    cases.sort_by(|a, b| order.compare(&a.name, &b.name));
+
+    for pair in cases.windows(2) {
+        if pair[0].name == pair[1].name {
+            return Err(std::io::Error::other(format!(
+                "case directories {:?} and {:?} both map to case name {:?}",
+                pair[0].dirname, pair[1].dirname, pair[0].name,
+            )));
+        }
+    }
+    Ok(cases)
+}
+
+/// Find the file for argument `arg` of the case directory `casedir` in `corpus_dir`
+///
+/// A case's own `<casedir>/<arg>` takes precedence, followed by the same file in each base case
+/// of its [case_chain], then a shared `<corpus_dir>/_shared/<arg>`, and finally a file
+/// `<corpus_dir>/<arg>`, so files common to many cases can live once in the corpus while
+/// individual cases override them. A directory at the top level of the corpus is a case, so it is
+/// never an argument of another case. Returns `None` if none of these exist, or an error if the
+/// case inheritance chain is invalid.
+pub fn resolve_case_file(
+    corpus_dir: &Path,
+    casedir: &Path,
+    arg: &str,
+) -> std::io::Result<Option<PathBuf>> {
+    let mut candidates: Vec<PathBuf> = case_chain(casedir)?
+        .into_iter()
+        .map(|dir| dir.join(arg))
+        .collect();
+    candidates.push(corpus_dir.join(SHARED_DIR).join(arg));
+    let toplevel = corpus_dir.join(arg);
+    Ok(candidates
+        .into_iter()
+        .find(|path| path.exists())
+        .or_else(|| Some(toplevel).filter(|path| path.is_file())))
+}
+
+/// List `casedir` followed by each base case it inherits files from
+///
+/// A case declares its base with an [EXTENDS_FILE] marker, a TOML document holding only an
+/// `extends` string such as `extends = "../base-case"`, where the path is relative to the case
+/// directory. A base case may itself extend another. It is an error for a marker to be malformed,
+/// for a base case directory not to exist, or for the chain to cycle back to a case already in it.
+pub fn case_chain(casedir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let canonical = |dir: &Path| dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+
+    let mut chain = vec![casedir.to_path_buf()];
+    let mut seen = vec![canonical(casedir)];
+    loop {
+        let dir = chain.last().unwrap();
+        let marker = dir.join(EXTENDS_FILE);
+        if !marker.is_file() {
+            return Ok(chain);
+        }
+        let located = |e: &dyn std::fmt::Display| {
+            std::io::Error::other(format!("{}: {}", marker.display(), e))
+        };
+
+        let text = std::fs::read_to_string(&marker).map_err(|e| located(&e))?;
+        let base = join_lexically(dir, &parse_extends(&text).map_err(|e| located(&e))?);
+        if !base.is_dir() {
+            return Err(located(&format!(
+                "base case {} is not a directory",
+                base.display()
+            )));
+        }
+        if seen.contains(&canonical(&base)) {
+            let cycle: Vec<String> = chain
+                .iter()
+                .chain(Some(&base))
+                .map(|dir| dir.display().to_string())
+                .collect();
+            return Err(located(&format!(
+                "case inheritance cycle: {}",
+                cycle.join(" -> ")
+            )));
+        }
+        seen.push(canonical(&base));
+        chain.push(base);
+    }
+}
+
+/// Join `path` onto `dir`, resolving `..` components against `dir` rather than leaving them in
+fn join_lexically(dir: &Path, path: &str) -> PathBuf {
+    use std::path::Component;
+
+    let mut joined = dir.to_path_buf();
+    for component in Path::new(path).components() {
+        match component {
+            Component::ParentDir
+                if matches!(joined.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                joined.pop();
+            }
+            Component::CurDir => {}
+            other => joined.push(other),
+        }
+    }
+    joined
+}
+
+/// Parse the base case path from the text of an [EXTENDS_FILE] marker
+fn parse_extends(text: &str) -> Result<String, String> {
+    let expected = "expected `extends = \"<base case path>\"`";
+    let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+    if let Some(key) = table.keys().find(|key| *key != "extends") {
+        return Err(format!("unknown key `{}`; {}", key, expected));
+    }
+    match table.get("extends") {
+        Some(toml::Value::String(path)) if !path.is_empty() => Ok(path.clone()),
+        _ => Err(expected.to_string()),
+    }
+}
+
+/// Losslessly escape a non-UTF-8 directory name into identifier characters
+///
+/// ASCII alphanumerics are kept, `_` becomes `__`, and every other byte becomes `_xHH` in lowercase
+/// hex, so the original bytes can always be recovered from the escaped name.
+pub fn escape_non_utf8(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    let mut escaped = String::new();
+    for &b in bytes {
+        match b {
+            b'_' => escaped.push_str("__"),
+            b if b.is_ascii_alphanumeric() => escaped.push(char::from(b)),
+            b => write!(escaped, "_x{:02x}", b).unwrap(),
+        }
+    }
+    escaped
+}
+
+/// List the names of all entries in a case directory, sorted by byte order
+pub fn list_case_entries(casedir: &Path) -> std::io::Result<Vec<String>> {
+    let mut names = vec![];
+    for entres in casedir.read_dir()? {
+        let entry = entres?;
+        names.push(entry.file_name().to_string_lossy().into_owned());
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// List every file beneath the directory `dir`, recursively and after traversing symlinks
+///
+/// Paths are relative to `dir` with `/` separators, sorted by byte order. Directories themselves
+/// are not listed, so an empty directory contributes nothing. It is an error for a name beneath
+/// `dir` not to be valid UTF-8, since it could not be represented in a listed path.
+pub fn list_tree(dir: &Path) -> std::io::Result<Vec<String>> {
+    fn walk(dir: &Path, prefix: &str, paths: &mut Vec<String>) -> std::io::Result<()> {
+        for entres in dir.read_dir()? {
+            let entry = entres?;
+            let name = entry.file_name().into_string().map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}: name is not valid UTF-8", entry.path().display()),
+                )
+            })?;
+            let relpath = format!("{}{}", prefix, name);
+            if entry.path().metadata()?.is_dir() {
+                walk(&entry.path(), &format!("{}/", relpath), paths)?;
+            } else {
+                paths.push(relpath);
+            }
+        }
+        Ok(())
+    }
+
+    let mut paths = vec![];
+    walk(dir, "", &mut paths)?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// Match `name` against a shell-style `pattern` where `*` matches any run of characters and `?`
+/// matches any single character
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // Position in `pattern` just after the last `*` and the position in `name` it is matched to:
+    let mut backtrack = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, n));
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((bp, bn)) => {
+                    p = bp;
+                    n = bn + 1;
+                    backtrack = Some((bp, bn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    while !a.is_empty() && !b.is_empty() {
+        let (achunk, arest) = split_chunk(a);
+        let (bchunk, brest) = split_chunk(b);
+
+        let ord = if is_digits(achunk) && is_digits(bchunk) {
+            let atrim = achunk.trim_start_matches('0');
+            let btrim = bchunk.trim_start_matches('0');
+            atrim.len().cmp(&btrim.len()).then_with(|| atrim.cmp(btrim))
+        } else {
+            achunk.cmp(bchunk)
+        };
+
+        if ord != Ordering::Equal {
+            return ord;
+        }
+        a = arest;
+        b = brest;
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Split off the leading run of either ASCII digits or non-digits
+fn split_chunk(s: &str) -> (&str, &str) {
+    let digits = s.starts_with(|c: char| c.is_ascii_digit());
+    let end = s
+        .find(|c: char| c.is_ascii_digit() != digits)
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+fn is_digits(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,260 @@
+use std::io::Result;
+use std::path::PathBuf;
+use target_test_dir::test_with_dir;
+
+#[cfg(unix)]
+#[test_with_dir]
+fn list_dir_with_symlink(testdir: PathBuf) -> Result<()> {
+    // Setup: create a corpus directory with a symlink to a case dir:
+    let corpus = testdir.join("corpus");
+    std::fs::create_dir(&corpus)?;
+    let extcase = testdir.join("external-casedir");
+    std::fs::create_dir(&extcase)?;
+    std::os::unix::fs::symlink(&extcase, corpus.join("casedir-link"))?;
+
+    // target code:
+    let casedirs = crate::listdir::list_dir(&corpus, Default::default())?;
+
+    // verify the results contain `extcase`:
+    assert_eq!(casedirs.len(), 1);
+
+    let foundcase = casedirs.into_iter().next().unwrap();
+    assert_eq!(foundcase.name, "casedir-link");
+    Ok(())
+}
+
+#[test_with_dir]
+fn list_dir_is_sorted(testdir: PathBuf) -> Result<()> {
+    use crate::listdir::SortOrder;
+
+    for name in ["case-10", "case-2", "beta", "alpha", "case-1"] {
+        std::fs::create_dir(testdir.join(name))?;
+    }
+    std::fs::write(testdir.join("README.md"), b"not a case")?;
+
+    let names = |order| -> Result<Vec<String>> {
+        let cases = crate::listdir::list_dir(&testdir, order)?;
+        Ok(cases.into_iter().map(|c| c.name).collect())
+    };
+
+    assert_eq!(
+        names(SortOrder::Lexical)?,
+        ["alpha", "beta", "case-1", "case-10", "case-2"],
+    );
+    assert_eq!(
+        names(SortOrder::Natural)?,
+        ["alpha", "beta", "case-1", "case-2", "case-10"],
+    );
+    Ok(())
+}
+
+#[test]
+fn natural_sort_order() {
+    use crate::listdir::SortOrder::Natural;
+    use std::cmp::Ordering::*;
+
+    assert_eq!(Natural.compare("a2", "a10"), Less);
+    assert_eq!(Natural.compare("a10", "a9b"), Greater);
+    assert_eq!(Natural.compare("v1.2", "v1.10"), Less);
+    assert_eq!(Natural.compare("a", "a1"), Less);
+    assert_eq!(Natural.compare("x007", "x7"), Less);
+    assert_eq!(Natural.compare("x7", "x7"), Equal);
+}
+
+#[test_with_dir]
+fn list_case_entries_includes_files_and_dirs(testdir: PathBuf) -> Result<()> {
+    std::fs::write(testdir.join("input"), b"")?;
+    std::fs::write(testdir.join("expectd"), b"")?;
+    std::fs::create_dir(testdir.join("subdir"))?;
+
+    assert_eq!(
+        crate::listdir::list_case_entries(&testdir)?,
+        ["expectd", "input", "subdir"],
+    );
+    Ok(())
+}
+
+#[test]
+fn glob_match() {
+    use crate::listdir::glob_match;
+
+    assert!(glob_match("README.md", "README.md"));
+    assert!(glob_match("*.md", "notes.md"));
+    assert!(glob_match("*.md", ".md"));
+    assert!(glob_match("case-?", "case-1"));
+    assert!(glob_match("*a*b*", "xxaxxbxx"));
+    assert!(glob_match("*", ""));
+    assert!(!glob_match("*.md", "notes.txt"));
+    assert!(!glob_match("case-?", "case-10"));
+    assert!(!glob_match("a*b", "ab_"));
+}
+
+#[cfg(unix)]
+#[test_with_dir]
+fn list_dir_with_non_utf8_name(testdir: PathBuf) -> Result<()> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dirname = OsStr::from_bytes(b"bad\xffname_1");
+    std::fs::create_dir(testdir.join(dirname))?;
+
+    let cases = crate::listdir::list_dir(&testdir, Default::default())?;
+    assert_eq!(cases.len(), 1);
+    assert_eq!(cases[0].name, "bad_xffname__1");
+    assert_eq!(cases[0].dirname, dirname);
+    assert!(!cases[0].is_utf8());
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test_with_dir]
+fn list_dir_rejects_colliding_names(testdir: PathBuf) -> Result<()> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    std::fs::create_dir(testdir.join(OsStr::from_bytes(b"a\xff")))?;
+    std::fs::create_dir(testdir.join("a_xff"))?;
+
+    assert!(crate::listdir::list_dir(&testdir, Default::default()).is_err());
+    Ok(())
+}
+
+#[test]
+fn escape_non_utf8() {
+    use crate::listdir::escape_non_utf8;
+
+    assert_eq!(escape_non_utf8(b"plain"), "plain");
+    assert_eq!(escape_non_utf8(b"a_b"), "a__b");
+    assert_eq!(escape_non_utf8(b"\x00-\xfe"), "_x00_x2d_xfe");
+}
+
+#[test_with_dir]
+fn shared_files(testdir: PathBuf) -> Result<()> {
+    use crate::listdir::{list_dir, resolve_case_file};
+
+    std::fs::create_dir_all(testdir.join("_shared"))?;
+    std::fs::create_dir_all(testdir.join("alpha"))?;
+    std::fs::create_dir_all(testdir.join("beta"))?;
+    std::fs::write(testdir.join("_shared/key"), "shared key")?;
+    std::fs::write(testdir.join("dictionary"), "root dictionary")?;
+    std::fs::write(testdir.join("beta/key"), "beta key")?;
+
+    let names: Vec<String> = list_dir(&testdir, Default::default())?
+        .into_iter()
+        .map(|case| case.name)
+        .collect();
+    assert_eq!(names, ["alpha", "beta"]);
+
+    let resolve = |case: &str, arg: &str| resolve_case_file(&testdir, &testdir.join(case), arg);
+    assert_eq!(resolve("alpha", "key")?, Some(testdir.join("_shared/key")));
+    assert_eq!(resolve("beta", "key")?, Some(testdir.join("beta/key")));
+    assert_eq!(
+        resolve("alpha", "dictionary")?,
+        Some(testdir.join("dictionary"))
+    );
+    assert_eq!(resolve("alpha", "missing")?, None);
+    // A sibling case is not a top level argument:
+    assert_eq!(resolve("alpha", "beta")?, None);
+    Ok(())
+}
+
+#[test_with_dir]
+fn case_inheritance(testdir: PathBuf) -> Result<()> {
+    use crate::listdir::{case_chain, resolve_case_file};
+
+    for case in ["base", "middle", "leaf"] {
+        std::fs::create_dir(testdir.join(case))?;
+    }
+    std::fs::write(testdir.join("base/input"), "base input")?;
+    std::fs::write(testdir.join("base/key"), "base key")?;
+    std::fs::write(testdir.join("middle/extends.toml"), "extends = '../base'\n")?;
+    std::fs::write(testdir.join("middle/key"), "middle key")?;
+    std::fs::write(
+        testdir.join("leaf/extends.toml"),
+        "# comment\n\nextends = \"..\\u002fmiddle\"  # inherit\n",
+    )?;
+    std::fs::write(testdir.join("key"), "root key")?;
+
+    assert_eq!(
+        case_chain(&testdir.join("leaf"))?,
+        [
+            testdir.join("leaf"),
+            testdir.join("middle"),
+            testdir.join("base")
+        ]
+    );
+
+    let resolve = |case: &str, arg: &str| resolve_case_file(&testdir, &testdir.join(case), arg);
+    assert_eq!(resolve("leaf", "key")?, Some(testdir.join("middle/key")));
+    assert_eq!(resolve("leaf", "input")?, Some(testdir.join("base/input")));
+    assert_eq!(resolve("base", "key")?, Some(testdir.join("base/key")));
+    assert_eq!(resolve("leaf", "missing")?, None);
+    Ok(())
+}
+
+#[test_with_dir]
+fn case_inheritance_errors(testdir: PathBuf) -> Result<()> {
+    use crate::listdir::case_chain;
+
+    let chain_error = |case: &str, marker: &str| -> Result<String> {
+        let casedir = testdir.join(case);
+        std::fs::create_dir_all(&casedir)?;
+        std::fs::write(casedir.join("extends.toml"), marker)?;
+        Ok(case_chain(&casedir).unwrap_err().to_string())
+    };
+
+    let e = chain_error("orphan", "extends = \"../missing\"")?;
+    assert!(e.ends_with("is not a directory"), "{}", e);
+
+    for malformed in ["", "base = \"../a\"", "extends = 3", "extends = \"\""] {
+        let e = chain_error("malformed", malformed)?;
+        assert!(e.contains("expected `extends = "), "{:?}: {}", malformed, e);
+    }
+
+    let e = chain_error("invalid", "extends = ../a")?;
+    assert!(e.contains("TOML parse error"), "{}", e);
+
+    let e = chain_error("twice", "extends = \"../a\"\nextends = \"../b\"")?;
+    assert!(e.contains("duplicate key `extends`"), "{}", e);
+
+    chain_error("a", "extends = \"../b\"")?;
+    let e = chain_error("b", "extends = \"../a\"")?;
+    let cycle = format!(
+        "case inheritance cycle: {} -> {} -> {}",
+        testdir.join("b").display(),
+        testdir.join("a").display(),
+        testdir.join("b").display(),
+    );
+    assert!(e.ends_with(&cycle), "{}", e);
+
+    let e = chain_error("self", "extends = \".\"")?;
+    assert!(e.contains("case inheritance cycle"), "{}", e);
+    Ok(())
+}
+
+#[test_with_dir]
+fn list_tree(testdir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(testdir.join("b/c"))?;
+    std::fs::create_dir_all(testdir.join("empty"))?;
+    std::fs::write(testdir.join("z"), "")?;
+    std::fs::write(testdir.join("b/a"), "")?;
+    std::fs::write(testdir.join("b/c/d"), "")?;
+
+    assert_eq!(crate::listdir::list_tree(&testdir)?, ["b/a", "b/c/d", "z"]);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test_with_dir]
+fn list_tree_rejects_non_utf8_name(testdir: PathBuf) -> Result<()> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    std::fs::create_dir_all(testdir.join("sub"))?;
+    std::fs::write(testdir.join("sub").join(OsStr::from_bytes(b"bad\xff")), "")?;
+
+    let e = crate::listdir::list_tree(&testdir).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    assert!(e.to_string().contains("not valid UTF-8"), "{}", e);
+    Ok(())
+}
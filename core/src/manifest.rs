@@ -0,0 +1,163 @@
+//! Checksum manifests of corpus files in the `sha256sum` format
+//!
+//! A manifest lives in the corpus directory and has one line per case file, giving the lowercase
+//! hex SHA-256 digest, two spaces, and the file path relative to the corpus directory with `/`
+//! separators, ie `<digest>  alpha/input`. This is the output of `sha256sum` run from the corpus
+//! directory, so upstream release checksums can be vendored as-is.
+
+use crate::listdir::{list_dir, SortOrder, SHARED_DIR};
+use std::collections::BTreeMap;
+use std::io::{Error, Result};
+use std::path::Path;
+
+/// The expected digest of each file in a corpus
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    /// Lowercase hex digests keyed by path relative to the corpus directory
+    digests: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Read and parse the manifest file at `path`
+    pub fn read(path: &Path) -> Result<Self> {
+        let located =
+            |e: &dyn std::fmt::Display| Error::other(format!("{}: {}", path.display(), e));
+        let text = std::fs::read_to_string(path).map_err(|e| located(&e))?;
+        Manifest::parse(&text).map_err(|e| located(&e))
+    }
+
+    /// Parse manifest text, accepting both `sha256sum` text and binary mode lines
+    ///
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> std::result::Result<Self, String> {
+        let mut digests = BTreeMap::new();
+        for (ix, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || format!("line {}: expected `<sha256 hex>  <path>`", ix + 1);
+            let (digest, rest) = line.split_once(' ').ok_or_else(malformed)?;
+            let path = rest
+                .strip_prefix(' ')
+                .or_else(|| rest.strip_prefix('*'))
+                .ok_or_else(malformed)?;
+            if digest.len() != 64 || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(malformed());
+            }
+            let path = path.strip_prefix("./").unwrap_or(path);
+
+            if digests
+                .insert(path.to_string(), digest.to_ascii_lowercase())
+                .is_some()
+            {
+                return Err(format!("line {}: duplicate entry for {}", ix + 1, path));
+            }
+        }
+        Ok(Manifest { digests })
+    }
+
+    /// The listed paths, relative to the corpus directory, in byte order
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.digests.keys().map(String::as_str)
+    }
+
+    /// Check the contents of the corpus file at `relpath` against its listed digest
+    pub fn verify(&self, relpath: &str, contents: &[u8]) -> std::result::Result<(), String> {
+        match self.digests.get(relpath) {
+            None => Err(format!("{} is not listed in the manifest", relpath)),
+            Some(expected) => {
+                let actual = sha256_hex(contents);
+                if &actual == expected {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{} has SHA-256 digest {}, but the manifest lists {}",
+                        relpath, actual, expected,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// List every file within `corpus_dir` with its digest, except the manifest file `name`
+    ///
+    /// This covers the case directories, the shared files directory, and files at the top level
+    /// of the corpus which cases may fall back to. Files ending in `.actual`, which hold saved
+    /// test outputs, are skipped.
+    pub fn generate(corpus_dir: &Path, name: &str) -> Result<Self> {
+        let mut digests = BTreeMap::new();
+        for case in list_dir(corpus_dir, SortOrder::Lexical)? {
+            let relcase = case.dirname.to_string_lossy().into_owned();
+            add_digests(&corpus_dir.join(&case.dirname), &relcase, &mut digests)?;
+        }
+        let shared = corpus_dir.join(SHARED_DIR);
+        if shared.is_dir() {
+            add_digests(&shared, SHARED_DIR, &mut digests)?;
+        }
+        for entres in corpus_dir.read_dir()? {
+            let entry = entres?;
+            let filename = entry.file_name().to_string_lossy().into_owned();
+            if filename != name && entry.path().metadata()?.is_file() {
+                add_digest(&entry.path(), filename, &mut digests)?;
+            }
+        }
+        Ok(Manifest { digests })
+    }
+
+    /// Regenerate the manifest file `name` in `corpus_dir`, returning the number of files listed
+    pub fn regenerate(corpus_dir: &Path, name: &str) -> Result<usize> {
+        let manifest = Manifest::generate(corpus_dir, name)?;
+        std::fs::write(corpus_dir.join(name), manifest.to_string())?;
+        Ok(manifest.digests.len())
+    }
+}
+
+impl std::fmt::Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (path, digest) in self.digests.iter() {
+            writeln!(f, "{}  {}", digest, path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The lowercase hex SHA-256 digest of `bytes`
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    use std::fmt::Write;
+
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, b| {
+            write!(hex, "{:02x}", b).unwrap();
+            hex
+        })
+}
+
+fn add_digests(dir: &Path, reldir: &str, digests: &mut BTreeMap<String, String>) -> Result<()> {
+    for entres in dir.read_dir()? {
+        let entry = entres?;
+        let path = entry.path();
+        let relpath = format!("{}/{}", reldir, entry.file_name().to_string_lossy());
+        if path.metadata()?.is_dir() {
+            // Saved actual trees are directories ending in `.actual`:
+            if !relpath.ends_with(".actual") {
+                add_digests(&path, &relpath, digests)?;
+            }
+        } else {
+            add_digest(&path, relpath, digests)?;
+        }
+    }
+    Ok(())
+}
+
+fn add_digest(path: &Path, relpath: String, digests: &mut BTreeMap<String, String>) -> Result<()> {
+    if !relpath.ends_with(".actual") {
+        digests.insert(relpath, sha256_hex(&std::fs::read(path)?));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+use super::{sha256_hex, Manifest};
+use std::io::Result;
+use std::path::PathBuf;
+use target_test_dir::test_with_dir;
+
+const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+const EMPTY: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+#[test]
+fn sha256() {
+    assert_eq!(sha256_hex(b"abc"), ABC);
+    assert_eq!(sha256_hex(b""), EMPTY);
+}
+
+#[test]
+fn parse_and_verify() {
+    let text = format!(
+        "# upstream release checksums\n{}  ./alpha/input\n\n{} *alpha/expected\n",
+        ABC.to_ascii_uppercase(),
+        EMPTY,
+    );
+    let manifest = Manifest::parse(&text).unwrap();
+    assert_eq!(
+        manifest.paths().collect::<Vec<_>>(),
+        ["alpha/expected", "alpha/input"]
+    );
+
+    assert_eq!(manifest.verify("alpha/input", b"abc"), Ok(()));
+    assert_eq!(manifest.verify("alpha/expected", b""), Ok(()));
+    assert_eq!(
+        manifest.verify("alpha/input", b""),
+        Err(format!(
+            "alpha/input has SHA-256 digest {}, but the manifest lists {}",
+            EMPTY, ABC
+        ))
+    );
+    assert_eq!(
+        manifest.verify("beta/input", b"abc"),
+        Err("beta/input is not listed in the manifest".to_string())
+    );
+}
+
+#[test]
+fn parse_rejects_malformed_lines() {
+    assert!(Manifest::parse("abc  alpha/input").is_err());
+    assert!(Manifest::parse(&format!("{}alpha/input", ABC)).is_err());
+    assert!(Manifest::parse(&format!("{}  a\n{}  a\n", ABC, ABC)).is_err());
+}
+
+#[test_with_dir]
+fn regenerate(testdir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(testdir.join("alpha/nested"))?;
+    std::fs::write(testdir.join("alpha/input"), "abc")?;
+    std::fs::write(testdir.join("alpha/input.actual"), "saved output")?;
+    std::fs::write(testdir.join("alpha/nested/expected"), "")?;
+    std::fs::create_dir_all(testdir.join("alpha/nested.actual"))?;
+    std::fs::write(testdir.join("alpha/nested.actual/expected"), "saved tree")?;
+    std::fs::create_dir_all(testdir.join("_shared"))?;
+    std::fs::write(testdir.join("_shared/key"), "")?;
+    std::fs::write(testdir.join("dictionary"), "abc")?;
+
+    assert_eq!(Manifest::regenerate(&testdir, "SHA256SUMS")?, 4);
+    assert_eq!(
+        std::fs::read_to_string(testdir.join("SHA256SUMS"))?,
+        format!(
+            "{}  _shared/key\n{}  alpha/input\n{}  alpha/nested/expected\n{}  dictionary\n",
+            EMPTY, ABC, EMPTY, ABC
+        )
+    );
+
+    // Regeneration excludes the manifest itself:
+    let manifest = Manifest::read(&testdir.join("SHA256SUMS"))?;
+    assert_eq!(manifest, Manifest::generate(&testdir, "SHA256SUMS")?);
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Optional per-case metadata files
+
+use std::path::Path;
+
+/// The metadata file names a case directory may contain, at most one of which may be present
+pub const META_FILES: &[&str] = &["meta.toml", "meta.json"];
+
+/// Descriptive metadata for a case, from a `meta.toml` or `meta.json` in its case directory
+///
+/// For example, a `meta.toml` might contain:
+///
+/// ```toml
+/// description = "A message with an empty payload"
+/// tags = ["fast", "edge-case"]
+/// references = ["RFC 9999 section 4.2"]
+/// ```
+///
+/// Every field is optional, and unknown fields are rejected to catch misspellings.
+#[derive(Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Meta {
+    /// What the case exercises
+    pub description: Option<String>,
+    /// Labels for selecting cases, such as `slow` or `edge-case`
+    pub tags: Vec<String>,
+    /// Specification sections, issues, or other sources for the case
+    pub references: Vec<String>,
+}
+
+impl Meta {
+    /// Read the metadata of the case directory `casedir`, or the default if it has no metadata
+    /// file
+    pub fn read(casedir: &Path) -> Result<Self, String> {
+        let present: Vec<&str> = META_FILES
+            .iter()
+            .copied()
+            .filter(|name| casedir.join(name).is_file())
+            .collect();
+
+        match present.as_slice() {
+            [] => Ok(Meta::default()),
+            [name] => {
+                let path = casedir.join(name);
+                let located = |e: &dyn std::fmt::Display| format!("{}: {}", path.display(), e);
+                let text = std::fs::read_to_string(&path).map_err(|e| located(&e))?;
+                if name.ends_with(".toml") {
+                    toml::from_str(&text).map_err(|e| located(&e))
+                } else {
+                    serde_json::from_str(&text).map_err(|e| located(&e))
+                }
+            }
+            _ => Err(format!(
+                "{} contains more than one metadata file: {}",
+                casedir.display(),
+                present.join(", "),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::Meta;
+use std::io::Result;
+use std::path::PathBuf;
+use target_test_dir::test_with_dir;
+
+#[test_with_dir]
+fn read_toml_and_json(testdir: PathBuf) -> Result<()> {
+    assert_eq!(Meta::read(&testdir), Ok(Meta::default()));
+
+    std::fs::write(
+        testdir.join("meta.toml"),
+        "description = \"empty payload\"\ntags = [\"fast\"]\n",
+    )?;
+    assert_eq!(
+        Meta::read(&testdir),
+        Ok(Meta {
+            description: Some("empty payload".to_string()),
+            tags: vec!["fast".to_string()],
+            references: vec![],
+        })
+    );
+
+    std::fs::remove_file(testdir.join("meta.toml"))?;
+    std::fs::write(
+        testdir.join("meta.json"),
+        r#"{"references": ["RFC 9999 section 4.2"]}"#,
+    )?;
+    assert_eq!(
+        Meta::read(&testdir),
+        Ok(Meta {
+            description: None,
+            tags: vec![],
+            references: vec!["RFC 9999 section 4.2".to_string()],
+        })
+    );
+    Ok(())
+}
+
+#[test_with_dir]
+fn read_rejects_bad_metadata(testdir: PathBuf) -> Result<()> {
+    std::fs::write(testdir.join("meta.toml"), "tag = [\"fast\"]\n")?;
+    let err = Meta::read(&testdir).unwrap_err();
+    assert!(err.contains("meta.toml"), "{}", err);
+    assert!(err.contains("unknown field"), "{}", err);
+
+    std::fs::write(testdir.join("meta.json"), "{}")?;
+    let err = Meta::read(&testdir).unwrap_err();
+    assert!(err.contains("more than one metadata file"), "{}", err);
+    Ok(())
+}
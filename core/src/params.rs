@@ -0,0 +1,353 @@
+// `darling`-generated `FromMeta` impls trip this lint for `#[darling(default)]` fields:
+#![allow(clippy::manual_unwrap_or_default)]
+
+use crate::error::Result;
+use crate::listdir::{SortOrder, EXTENDS_FILE};
+use crate::tags::TagFilter;
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use std::path::{Path, PathBuf};
+
+/// The parsed arguments of a `test_vectors` or `bench_vectors` attribute
+#[derive(Debug)]
+pub struct MacroParams {
+    /// The corpus directory, resolved against the crate manifest directory
+    pub dir: PathBuf,
+    /// The corpus directory as given, relative to the crate manifest directory
+    pub relative_dir: PathBuf,
+    /// Run cases when the criterion function is defined, for doctests
+    pub doctest: bool,
+    /// The order of generated cases
+    pub sort: SortOrder,
+    /// Permit a corpus without cases
+    pub allow_empty: bool,
+    /// The minimum number of cases the corpus must contain
+    pub min_cases: Option<usize>,
+    /// Reject case directory entries which no argument uses
+    pub strict: bool,
+    /// Patterns of case directory entries exempt from `strict`
+    pub ignore: Vec<String>,
+    /// Named implementations to instantiate a generic criterion function with
+    pub types: Vec<(syn::Ident, syn::Type)>,
+    /// Arguments which take literal values rather than case files, with their values
+    pub params: Vec<(syn::Ident, Vec<syn::Expr>)>,
+    /// Every case is expected to fail, as if it contained an [EXPECT_ERROR_FILE]
+    pub expect_error: bool,
+    /// Write mismatched actual values beside the case files they were compared with
+    pub write_actual: bool,
+    /// A `sha256sum`-format manifest which case files must match, resolved against `dir`
+    pub manifest: Option<PathBuf>,
+    /// Generate only cases whose metadata tags this filter selects
+    pub tags: TagFilter,
+    /// A binary target of the crate to run with each case's [CMD_FILES] before the criterion
+    pub bin: Option<String>,
+}
+
+#[derive(Debug, FromMeta)]
+struct RawMacroParams {
+    dir: String,
+
+    #[darling(default)]
+    doctest: bool,
+
+    #[darling(default)]
+    sort: Sort,
+
+    #[darling(default)]
+    allow_empty: bool,
+
+    #[darling(default)]
+    min_cases: Option<usize>,
+
+    #[darling(default)]
+    strict: bool,
+
+    #[darling(multiple)]
+    ignore: Vec<String>,
+
+    #[darling(default)]
+    types: Bindings<syn::Type>,
+
+    #[darling(default)]
+    params: Bindings<syn::ExprArray>,
+
+    #[darling(default)]
+    expect_error: bool,
+
+    #[darling(default)]
+    write_actual: bool,
+
+    #[darling(default)]
+    manifest: Option<String>,
+
+    #[darling(default)]
+    tags: StrList,
+
+    #[darling(default)]
+    bin: Option<String>,
+}
+
+impl MacroParams {
+    /// Parse attribute arguments, resolving `dir` against the `CARGO_MANIFEST_DIR` environment
+    /// variable as during macro expansion
+    pub fn parse(tokens: TokenStream) -> Result<Self> {
+        use syn::spanned::Spanned;
+
+        let span = tokens.span();
+        let manifestdir = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| {
+            syn::Error::new(
+                span,
+                format!(
+                    "could not access CARGO_MANIFEST_DIR from environment: {}",
+                    e
+                ),
+            )
+        })?;
+
+        MacroParams::parse_in(tokens, Path::new(&manifestdir))
+    }
+
+    /// Parse attribute arguments, resolving `dir` against the crate directory `manifestdir`
+    pub fn parse_in(tokens: TokenStream, manifestdir: &Path) -> Result<Self> {
+        let args = parse_attribute_args(tokens)?;
+        let raw = RawMacroParams::from_list(&args)?;
+
+        let relative_dir = PathBuf::from(raw.dir);
+        let dir = manifestdir.join(&relative_dir);
+        let manifest = raw.manifest.map(|name| dir.join(name));
+
+        Ok(MacroParams {
+            dir,
+            relative_dir,
+            doctest: raw.doctest,
+            sort: raw.sort.0,
+            allow_empty: raw.allow_empty,
+            min_cases: raw.min_cases,
+            strict: raw.strict,
+            ignore: raw.ignore,
+            types: raw.types.0,
+            params: raw
+                .params
+                .0
+                .into_iter()
+                .map(|(name, values)| (name, values.elems.into_iter().collect()))
+                .collect(),
+            expect_error: raw.expect_error,
+            write_actual: raw.write_actual,
+            manifest,
+            tags: TagFilter::new(raw.tags.0),
+            bin: raw.bin,
+        })
+    }
+
+    /// Guard against a corpus silently generating too few tests, ie from a typo in `dir`
+    pub fn check_case_count(&self, count: usize) -> std::result::Result<(), String> {
+        if let Some(min) = self.min_cases {
+            if count < min {
+                return Err(format!(
+                    "corpus directory {:?} contains {} cases, fewer than min_cases = {}",
+                    self.dir, count, min,
+                ));
+            }
+        } else if count == 0 && !self.allow_empty {
+            return Err(format!(
+                "corpus directory {:?} contains no case directories; use `allow_empty = true` if this is intended",
+                self.dir,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether a case directory entry is exempt from `strict` checking
+    pub fn is_ignored(&self, name: &str) -> bool {
+        use crate::listdir::glob_match;
+
+        STRICT_ALLOWLIST
+            .iter()
+            .copied()
+            .chain(self.ignore.iter().map(String::as_str))
+            .chain(self.bin.iter().flat_map(|_| CMD_FILES.iter().copied()))
+            .any(|pat| glob_match(pat, name))
+    }
+}
+
+/// A `darling` adapter for [SortOrder], which does not depend on `darling` itself
+#[derive(Debug, Default)]
+struct Sort(SortOrder);
+
+impl FromMeta for Sort {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        value
+            .parse()
+            .map(Sort)
+            .map_err(|_| darling::Error::unknown_value(value))
+    }
+}
+
+/// A `darling` adapter for an array of string literals, ie `tags = ["fast", "!network"]`
+#[derive(Debug, Default)]
+struct StrList(Vec<String>);
+
+impl FromMeta for StrList {
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        let array = syn::ExprArray::from_value(value)?;
+        array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) => Ok(s.value()),
+                other => Err(darling::Error::custom("expected a string literal").with_span(other)),
+            })
+            .collect::<darling::Result<_>>()
+            .map(StrList)
+    }
+}
+
+/// An ordered list of `name = value` pairs from a nested macro argument, ie `types(A = X, B = Y)`
+#[derive(Debug)]
+struct Bindings<T>(Vec<(syn::Ident, T)>);
+
+impl<T> Default for Bindings<T> {
+    fn default() -> Self {
+        Bindings(vec![])
+    }
+}
+
+impl<T> FromMeta for Bindings<T>
+where
+    T: FromMeta,
+{
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        use syn::{Meta, NestedMeta};
+
+        let mut bindings: Vec<(syn::Ident, T)> = vec![];
+        for item in items {
+            let nv = match item {
+                NestedMeta::Meta(Meta::NameValue(nv)) => nv,
+                other => {
+                    return Err(
+                        darling::Error::unsupported_format("non `name = value` item")
+                            .with_span(other),
+                    )
+                }
+            };
+            let ident = nv
+                .path
+                .get_ident()
+                .ok_or_else(|| darling::Error::custom("expected identifier").with_span(&nv.path))?;
+            if bindings.iter().any(|(prev, _)| prev == ident) {
+                return Err(darling::Error::duplicate_field(&ident.to_string()).with_span(ident));
+            }
+            let value = T::from_value(&nv.lit).map_err(|e| e.with_span(&nv.lit))?;
+            bindings.push((ident.clone(), value));
+        }
+        Ok(Bindings(bindings))
+    }
+}
+
+/// The case file marking a case whose conversion or criterion is expected to return an error
+///
+/// Non-empty contents, after trimming whitespace, must appear in the error's `Display` text.
+pub const EXPECT_ERROR_FILE: &str = "expect_error";
+
+/// The case files driving a `bin` command, in the order they are applied: its command line
+/// arguments, its environment variables, its standard input, and its expected standard output,
+/// standard error, and exit status
+pub const CMD_FILES: &[&str] = &["args", "env", "stdin", "stdout", "stderr", "status"];
+
+/// Patterns of case directory entries which `strict` mode never reports as unused
+const STRICT_ALLOWLIST: &[&str] = &[
+    "README.md",
+    EXPECT_ERROR_FILE,
+    EXTENDS_FILE,
+    "*.actual",
+    "meta.toml",
+    "meta.json",
+];
+
+/// Parse the tokens inside an attribute's parentheses into `AttributeArgs`
+///
+/// We cannot use `parse_macro_input!` which returns a `TokenStream` and `AttributeArgs` does not
+/// impl `syn::parse::Parse`, so we do this by hand. :-<
+///
+/// Beyond `name = <literal>`, this accepts bare flags (`name`), nested lists (`name(...)`), and
+/// non-literal values such as types or arrays (`name = Vec<u8>`). Non-literal values are passed
+/// to `darling` as string literals of their tokens, which is the form its `syn` parsing impls
+/// expect.
+pub fn parse_attribute_args(tokens: TokenStream) -> Result<syn::AttributeArgs> {
+    use syn::parse::Parser;
+    use syn::punctuated::Punctuated;
+    use syn::Token;
+
+    let parser = |input: syn::parse::ParseStream| {
+        Punctuated::<syn::NestedMeta, Token![,]>::parse_terminated_with(input, parse_nested_meta)
+    };
+
+    Ok(parser.parse2(tokens)?.into_iter().collect())
+}
+
+fn parse_nested_meta(input: syn::parse::ParseStream) -> syn::Result<syn::NestedMeta> {
+    use syn::punctuated::Punctuated;
+    use syn::{Meta, NestedMeta, Token};
+
+    let path: syn::Path = input.parse()?;
+
+    let meta = if input.peek(syn::token::Paren) {
+        let content;
+        let paren_token = syn::parenthesized!(content in input);
+        let nested = Punctuated::parse_terminated_with(&content, parse_nested_meta)?;
+        Meta::List(syn::MetaList {
+            path,
+            paren_token,
+            nested,
+        })
+    } else if input.peek(Token![=]) {
+        let eq_token = input.parse()?;
+        let lit = parse_value(input)?;
+        Meta::NameValue(syn::MetaNameValue {
+            path,
+            eq_token,
+            lit,
+        })
+    } else {
+        Meta::Path(path)
+    };
+
+    Ok(NestedMeta::Meta(meta))
+}
+
+fn parse_value(input: syn::parse::ParseStream) -> syn::Result<syn::Lit> {
+    use quote::ToTokens;
+    use syn::parse::discouraged::Speculative;
+    use syn::parse::{Parse, ParseStream};
+    use syn::Token;
+
+    /// Speculatively parse a complete value, ie one followed by a comma or the end of input
+    fn attempt<T: Parse>(input: ParseStream) -> Option<T> {
+        let fork = input.fork();
+        let value = fork.parse().ok()?;
+        if fork.is_empty() || fork.peek(Token![,]) {
+            input.advance_to(&fork);
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    if let Some(lit) = attempt::<syn::Lit>(input) {
+        return Ok(lit);
+    }
+
+    let span = input.span();
+    let tokens = match attempt::<syn::Type>(input) {
+        Some(ty) => ty.into_token_stream(),
+        None => input.parse::<syn::Expr>()?.into_token_stream(),
+    };
+    Ok(syn::Lit::Str(syn::LitStr::new(&tokens.to_string(), span)))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,154 @@
+use super::MacroParams;
+use quote::quote;
+
+#[test]
+fn test_parse_foo() {
+    let input = quote! {
+        dir = "foo"
+    };
+
+    let mp = MacroParams::parse(input).unwrap();
+
+    assert_eq!(mp.dir.file_name().and_then(|s| s.to_str()), Some("foo"));
+}
+
+#[test]
+fn test_parse_sort() {
+    use crate::listdir::SortOrder;
+
+    let mp = MacroParams::parse(quote! { dir = "foo" }).unwrap();
+    assert_eq!(mp.sort, SortOrder::Lexical);
+
+    let mp = MacroParams::parse(quote! { dir = "foo", sort = "natural" }).unwrap();
+    assert_eq!(mp.sort, SortOrder::Natural);
+
+    assert!(MacroParams::parse(quote! { dir = "foo", sort = "random" }).is_err());
+}
+
+#[test]
+fn test_check_case_count() {
+    let mp = MacroParams::parse(quote! { dir = "foo" }).unwrap();
+    assert!(mp.check_case_count(0).is_err());
+    assert!(mp.check_case_count(1).is_ok());
+
+    let mp = MacroParams::parse(quote! { dir = "foo", allow_empty = true }).unwrap();
+    assert!(mp.check_case_count(0).is_ok());
+
+    let mp = MacroParams::parse(quote! { dir = "foo", min_cases = 3 }).unwrap();
+    assert!(mp.check_case_count(2).is_err());
+    assert!(mp.check_case_count(3).is_ok());
+}
+
+#[test]
+fn test_is_ignored() {
+    let mp = MacroParams::parse(quote! { dir = "foo", strict = true }).unwrap();
+    assert!(mp.strict);
+    assert!(mp.is_ignored("README.md"));
+    assert!(mp.is_ignored("expect_error"));
+    assert!(mp.is_ignored("expected.actual"));
+    assert!(mp.is_ignored("meta.toml"));
+    assert!(!mp.is_ignored("notes.txt"));
+
+    let mp = MacroParams::parse(quote! {
+        dir = "foo", strict = true, ignore = "*.txt", ignore = "LICENSE"
+    })
+    .unwrap();
+    assert!(mp.is_ignored("README.md"));
+    assert!(mp.is_ignored("notes.txt"));
+    assert!(mp.is_ignored("LICENSE"));
+    assert!(!mp.is_ignored("expectd"));
+    assert!(!mp.is_ignored("stdout"));
+
+    let mp = MacroParams::parse(quote! { dir = "foo", strict = true, bin = "mytool" }).unwrap();
+    assert_eq!(mp.bin.as_deref(), Some("mytool"));
+    assert!(mp.is_ignored("args"));
+    assert!(mp.is_ignored("stdout"));
+    assert!(mp.is_ignored("status"));
+}
+
+#[test]
+fn test_parse_types() {
+    let mp = MacroParams::parse(quote! {
+        dir = "foo", types(Soft = Sha256Soft, Generic = hash::Generic<u8, 32>)
+    })
+    .unwrap();
+
+    let types: Vec<(String, String)> = mp
+        .types
+        .iter()
+        .map(|(name, ty)| (name.to_string(), quote! { #ty }.to_string()))
+        .collect();
+    assert_eq!(
+        types,
+        [
+            ("Soft".to_string(), "Sha256Soft".to_string()),
+            (
+                "Generic".to_string(),
+                quote! { hash::Generic<u8, 32> }.to_string()
+            ),
+        ]
+    );
+
+    assert!(MacroParams::parse(quote! { dir = "foo", types(A = X, A = Y) }).is_err());
+}
+
+#[test]
+fn test_parse_manifest() {
+    let mp = MacroParams::parse(quote! { dir = "foo" }).unwrap();
+    assert_eq!(mp.manifest, None);
+
+    let mp = MacroParams::parse(quote! { dir = "foo", manifest = "SHA256SUMS" }).unwrap();
+    assert_eq!(mp.manifest, Some(mp.dir.join("SHA256SUMS")));
+}
+
+#[test]
+fn test_parse_tags() {
+    use crate::tags::TagFilter;
+
+    let mp = MacroParams::parse(quote! { dir = "foo" }).unwrap();
+    assert!(mp.tags.is_empty());
+
+    let mp = MacroParams::parse(quote! { dir = "foo", tags = ["fast", "!network"] }).unwrap();
+    assert_eq!(mp.tags, TagFilter::parse("fast,!network"));
+
+    assert!(MacroParams::parse(quote! { dir = "foo", tags = [fast] }).is_err());
+}
+
+#[test]
+fn test_parse_flag() {
+    let mp = MacroParams::parse(quote! { dir = "foo", strict }).unwrap();
+    assert!(mp.strict);
+    assert!(!mp.expect_error);
+
+    let mp = MacroParams::parse(quote! { dir = "foo", expect_error }).unwrap();
+    assert!(mp.expect_error);
+}
+
+#[test]
+fn test_parse_params() {
+    let mp = MacroParams::parse(quote! {
+        dir = "foo", params(level = [1, 6, 9], mode = ["fast"])
+    })
+    .unwrap();
+
+    let params: Vec<(String, Vec<String>)> = mp
+        .params
+        .iter()
+        .map(|(name, values)| {
+            (
+                name.to_string(),
+                values.iter().map(|v| quote! { #v }.to_string()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        params,
+        [
+            (
+                "level".to_string(),
+                vec!["1".to_string(), "6".to_string(), "9".to_string()]
+            ),
+            ("mode".to_string(), vec!["\"fast\"".to_string()]),
+        ]
+    );
+}
@@ -0,0 +1,155 @@
+//! Find criterion functions in a crate's source code without compiling it
+
+use crate::fnargs::{is_case_temp_dir, is_tree, parse_fn_args};
+use crate::params::MacroParams;
+use std::io::{Error, Result};
+use std::path::{Path, PathBuf};
+
+/// The crate subdirectories searched for rust sources
+const SOURCE_DIRS: &[&str] = &["src", "tests", "benches", "examples"];
+
+/// A criterion function annotated with `test_vectors` or `bench_vectors`
+#[derive(Debug)]
+pub struct Usage {
+    /// The source file containing the criterion function
+    pub file: PathBuf,
+    /// The criterion function name
+    pub function: String,
+    /// The parsed attribute arguments, with `dir` resolved against the crate directory
+    pub params: MacroParams,
+    /// The criterion function argument names, except those taking a `CaseTempDir`
+    pub argnames: Vec<String>,
+    /// Whether an argument takes a `CaseTempDir`, giving the criterion every case directory entry
+    pub copies_case: bool,
+    /// The argument names taking a `Tree`, which are read from case subdirectories
+    pub tree_args: Vec<String>,
+}
+
+impl Usage {
+    /// The argument names which are loaded from case files, ie excluding `params(...)` arguments
+    pub fn file_args(&self) -> impl Iterator<Item = &str> {
+        self.argnames
+            .iter()
+            .filter(|arg| !self.params.params.iter().any(|(p, _)| p == arg))
+            .map(String::as_str)
+    }
+
+    /// Whether this usage reads the corpus directory `dir`
+    pub fn uses_corpus(&self, dir: &Path) -> bool {
+        same_path(&self.params.dir, dir)
+    }
+}
+
+/// Find every criterion function in the rust sources of the crate at `crate_dir`
+///
+/// Sources are `.rs` files under the `src`, `tests`, `benches`, and `examples` directories.
+/// Usages are returned in file path order, then source order.
+pub fn scan_crate(crate_dir: &Path) -> Result<Vec<Usage>> {
+    let mut files = vec![];
+    for sub in SOURCE_DIRS {
+        let dir = crate_dir.join(sub);
+        if dir.is_dir() {
+            find_rust_files(&dir, &mut files)?;
+        }
+    }
+    files.sort();
+
+    let mut usages = vec![];
+    for file in files {
+        scan_file(crate_dir, &file, &mut usages)?;
+    }
+    Ok(usages)
+}
+
+/// Find the criterion functions within a single rust source file
+pub fn scan_file(crate_dir: &Path, file: &Path, usages: &mut Vec<Usage>) -> Result<()> {
+    let source = std::fs::read_to_string(file)?;
+    let ast = syn::parse_file(&source).map_err(|e| located(file, e))?;
+    scan_items(crate_dir, file, &ast.items, usages)
+}
+
+fn scan_items(
+    crate_dir: &Path,
+    file: &Path,
+    items: &[syn::Item],
+    usages: &mut Vec<Usage>,
+) -> Result<()> {
+    for item in items {
+        match item {
+            syn::Item::Fn(itemfn) => {
+                for attr in itemfn.attrs.iter().filter(|a| is_vectors_attr(a)) {
+                    let tokens = attr
+                        .parse_args_with(|input: syn::parse::ParseStream| input.parse())
+                        .map_err(|e| located(file, e))?;
+                    let params =
+                        MacroParams::parse_in(tokens, crate_dir).map_err(|e| located(file, e))?;
+                    let (argnames, argtypes) =
+                        parse_fn_args(&itemfn.sig).map_err(|e| located(file, e))?;
+                    let copies_case = argtypes.iter().any(is_case_temp_dir);
+                    let tree_args = argnames
+                        .iter()
+                        .zip(&argtypes)
+                        .filter(|(_, ty)| is_tree(ty))
+                        .map(|(name, _)| name.clone())
+                        .collect();
+                    let argnames = argnames
+                        .into_iter()
+                        .zip(argtypes)
+                        .filter(|(_, ty)| !is_case_temp_dir(ty))
+                        .map(|(name, _)| name)
+                        .collect();
+
+                    usages.push(Usage {
+                        file: file.to_path_buf(),
+                        function: itemfn.sig.ident.to_string(),
+                        params,
+                        argnames,
+                        copies_case,
+                        tree_args,
+                    });
+                }
+            }
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => scan_items(crate_dir, file, items, usages)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn is_vectors_attr(attr: &syn::Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .map(|seg| seg.ident == "test_vectors" || seg.ident == "bench_vectors")
+        .unwrap_or(false)
+}
+
+fn find_rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entres in dir.read_dir()? {
+        let path = entres?.path();
+        if path.is_dir() {
+            find_rust_files(&path, files)?;
+        } else if path.extension().map(|ext| ext == "rs").unwrap_or(false) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn located<E: std::fmt::Display>(file: &Path, e: E) -> Error {
+    Error::other(format!("{}: {}", file.display(), e))
+}
+
+/// Compare paths by their canonical form when they exist, or literally otherwise
+pub fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests;
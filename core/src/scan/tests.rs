@@ -0,0 +1,61 @@
+use std::io::Result;
+use std::path::PathBuf;
+use target_test_dir::test_with_dir;
+
+#[test_with_dir]
+fn scan_crate_finds_criteria(testdir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(testdir.join("src"))?;
+    std::fs::create_dir_all(testdir.join("tests/nested"))?;
+    std::fs::write(
+        testdir.join("src/lib.rs"),
+        r#"
+        pub fn not_a_criterion() {}
+
+        #[cfg(test)]
+        mod tests {
+            #[test_vectors::test_vectors(dir = "vectors", params(level = [1, 2]))]
+            fn check(input: &[u8], level: u32, expected: &[u8]) {}
+        }
+        "#,
+    )?;
+    std::fs::write(
+        testdir.join("tests/nested/other.rs"),
+        r#"
+        #[bench_vectors(dir = "vectors")]
+        fn bench(input: &[u8], expected_tree: test_vectors::Tree) {}
+        "#,
+    )?;
+
+    let usages = crate::scan::scan_crate(&testdir)?;
+    let summary: Vec<(&str, Vec<&str>)> = usages
+        .iter()
+        .map(|u| (u.function.as_str(), u.file_args().collect()))
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            ("check", vec!["input", "expected"]),
+            ("bench", vec!["input", "expected_tree"]),
+        ]
+    );
+    assert!(usages[0].tree_args.is_empty());
+    assert_eq!(usages[1].tree_args, ["expected_tree"]);
+    assert!(usages
+        .iter()
+        .all(|u| u.uses_corpus(&testdir.join("vectors"))));
+    Ok(())
+}
+
+#[test_with_dir]
+fn scan_crate_reports_parse_errors(testdir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(testdir.join("src"))?;
+    std::fs::write(
+        testdir.join("src/lib.rs"),
+        "#[test_vectors(nodir = 3)] fn f() {}",
+    )?;
+
+    let err = crate::scan::scan_crate(&testdir).unwrap_err();
+    assert!(err.to_string().contains("lib.rs"), "{}", err);
+    Ok(())
+}
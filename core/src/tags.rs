@@ -0,0 +1,67 @@
+//! Select cases by the tags in their metadata
+
+/// A tag selection such as `fast,!network`
+///
+/// A case is selected if it has at least one included tag, or if no tags are included, and it has
+/// none of the excluded tags, which are written with a leading `!`. The empty filter selects
+/// every case.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl TagFilter {
+    /// Build a filter from individual patterns, ie `["fast", "!network"]`
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut filter = TagFilter::default();
+        for pattern in patterns {
+            let pattern = pattern.as_ref().trim();
+            match pattern.strip_prefix('!') {
+                Some(tag) => filter.exclude.push(tag.trim().to_string()),
+                None if pattern.is_empty() => {}
+                None => filter.include.push(pattern.to_string()),
+            }
+        }
+        filter
+    }
+
+    /// Parse a comma separated list of patterns, ie `fast,!network`
+    pub fn parse(list: &str) -> Self {
+        TagFilter::new(list.split(','))
+    }
+
+    /// Whether this filter selects every case
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether a case with `tags` is selected
+    pub fn matches<S>(&self, tags: &[S]) -> bool
+    where
+        S: AsRef<str>,
+    {
+        let has = |tag: &String| tags.iter().any(|t| t.as_ref() == tag);
+
+        (self.include.is_empty() || self.include.iter().any(has)) && !self.exclude.iter().any(has)
+    }
+}
+
+impl std::fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let patterns: Vec<String> = self
+            .include
+            .iter()
+            .cloned()
+            .chain(self.exclude.iter().map(|tag| format!("!{}", tag)))
+            .collect();
+        write!(f, "{}", patterns.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use super::TagFilter;
+
+#[test]
+fn empty_filter_selects_everything() {
+    let filter = TagFilter::parse("");
+    assert!(filter.is_empty());
+    assert!(filter.matches::<&str>(&[]));
+    assert!(filter.matches(&["slow"]));
+}
+
+#[test]
+fn include_and_exclude() {
+    let filter = TagFilter::parse("fast, edge-case,!network");
+    assert!(!filter.is_empty());
+    assert_eq!(filter.to_string(), "fast,edge-case,!network");
+
+    assert!(filter.matches(&["fast"]));
+    assert!(filter.matches(&["slow", "edge-case"]));
+    assert!(!filter.matches(&["slow"]));
+    assert!(!filter.matches::<&str>(&[]));
+    assert!(!filter.matches(&["fast", "network"]));
+}
+
+#[test]
+fn exclude_only() {
+    let filter = TagFilter::new(["!network"]);
+    assert!(filter.matches::<&str>(&[]));
+    assert!(filter.matches(&["fast"]));
+    assert!(!filter.matches(&["network"]));
+}
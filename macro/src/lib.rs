@@ -1,7 +1,8 @@
-pub(crate) mod error;
-pub(crate) mod fnargs;
-pub(crate) mod listdir;
-pub(crate) mod params;
+//! The proc macros re-exported by the [test-vectors](https://docs.rs/test-vectors) crate
+//!
+//! This crate is not usable on its own: generated code refers to runtime items by the absolute
+//! path `::test_vectors`, so the `test-vectors` crate must be a dependency under that name. Depend
+//! on `test-vectors` and use its re-exports rather than depending on this crate directly.
 mod transform;
 
 use proc_macro::TokenStream;
@@ -15,3 +16,13 @@ use proc_macro::TokenStream;
 pub fn test_vectors(args: TokenStream, input: TokenStream) -> TokenStream {
     self::transform::test_vectors(args, input)
 }
+
+/// Generate a `criterion` benchmark group from the annotated criterion function and input files
+///
+/// Usage: `bench_vectors(dir = "<path to corpus directory>")`
+///
+/// See the `test-vectors` crate documentation for full documentation.
+#[proc_macro_attribute]
+pub fn bench_vectors(args: TokenStream, input: TokenStream) -> TokenStream {
+    self::transform::bench_vectors(args, input)
+}
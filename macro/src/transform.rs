@@ -1,5 +1,7 @@
-use crate::error::{Error, Result};
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
+use std::path::PathBuf;
+use test_vectors_core::error::{Error, Result};
+use test_vectors_core::params::MacroParams;
 
 pub(crate) fn test_vectors<TS>(args: TS, input: TS) -> TS
 where
@@ -12,36 +14,64 @@ where
     )
 }
 
+pub(crate) fn bench_vectors<TS>(args: TS, input: TS) -> TS
+where
+    TokenStream: From<TS>,
+    TS: From<TokenStream>,
+{
+    TS::from(
+        bench_vectors_result(TokenStream::from(args), TokenStream::from(input))
+            .unwrap_or_else(Error::into_compile_error),
+    )
+}
+
 fn test_vectors_result(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
-    use crate::fnargs::parse_fn_args;
-    use crate::listdir::list_dir;
-    use crate::params::MacroParams;
     use quote::quote;
-    use syn::spanned::Spanned;
 
-    let spanargs = args.span();
-    let spaninput = input.span();
-    let params = MacroParams::parse(args)?;
-    let mut implfn: syn::ItemFn = syn::parse2(input)?;
+    let Expansion {
+        params,
+        implfn,
+        basename,
+        spanargs,
+        calls,
+        inputs,
+    } = Expansion::new(args, input)?;
+    let inputs = track_inputs(&inputs);
+
+    // Cargo only provides the paths of the crate's binaries to integration tests and benchmarks:
+    if let Some(bin) = &params.bin {
+        let var = format!("CARGO_BIN_EXE_{}", bin);
+        if std::env::var_os(&var).is_none() {
+            return Err(syn::Error::new(
+                spanargs,
+                format!(
+                    "`bin = {:?}` requires cargo to set {}, which it only does for a binary target of this crate when compiling an integration test or benchmark",
+                    bin, var,
+                ),
+            )
+            .into());
+        }
+    }
 
-    // Save the impl fn name and rename it:
-    let basename = implfn.sig.ident.to_string();
-    implfn.sig.ident = syn::Ident::new(&format!("impl_{}", &basename), implfn.sig.ident.span());
     let implname = &implfn.sig.ident;
 
     // Save the return type to propagate it:
     let tyret = &implfn.sig.output;
 
-    let (argnames, argtypes) =
-        parse_fn_args(&implfn.sig).map_err(|s| syn::Error::new(spaninput, s))?;
-    let casenames = list_dir(&params.dir).map_err(|e| syn::Error::new(spanargs, e.to_string()))?;
-
     let mut casefns = vec![];
-    for casename in casenames {
-        let casefnname = syn::Ident::new(&format!("{}_{}", &basename, &casename), spanargs);
-        let argpaths = argnames
-            .iter()
-            .map(|arg| params.dir.join(&casename).join(arg).display().to_string());
+    for call in calls {
+        let casefnname = format!("{}_{}", &basename, &call.name);
+        if syn::parse_str::<syn::Ident>(&casefnname).is_err() {
+            return Err(syn::Error::new(
+                spanargs,
+                format!(
+                    "case directory {:?} does not produce a valid test name: {}",
+                    call.casedir, casefnname,
+                ),
+            )
+            .into());
+        }
+        let casefnname = syn::Ident::new(&casefnname, spanargs);
 
         let (testattr, execdoctest) = if params.doctest {
             (
@@ -57,24 +87,702 @@ fn test_vectors_result(args: TokenStream, input: TokenStream) -> Result<TokenStr
             (quote! { #[test] }, quote! {})
         };
 
+        // Type variant names are user-chosen, ie `Soft`, so may not be snake case:
+        let lintattr = if call.nonsnake {
+            quote! { #[allow(non_snake_case)] }
+        } else {
+            quote! {}
+        };
+
+        let CaseCall {
+            casename,
+            casedir,
+            utf8,
+            turbofish,
+            args,
+            expect_error,
+            meta,
+            cmd,
+            ..
+        } = call;
+
+        // Register the case so runtime helpers can refer to it:
+        let corpus = params.relative_dir.display().to_string();
+        let dir = if utf8 {
+            let casedir = casedir.display().to_string();
+            quote! { Some(#casedir) }
+        } else {
+            quote! { None }
+        };
+        let write_actual = params.write_actual;
+        let caseinfo = quote! {
+            ::test_vectors::CaseInfo::new(#casename, #corpus, #dir, #write_actual, #meta)
+        };
+
+        // Cases deselected by tag at runtime pass without running, which needs a success value:
+        let skipped = if returns_unit(tyret) || expect_error.is_some() {
+            quote! {}
+        } else if returns_unit_result(tyret) {
+            quote! { ::std::result::Result::Ok(()) }
+        } else {
+            return Err(syn::Error::new_spanned(
+                tyret,
+                "criterion functions must return `()` or a `Result` with success type `()`, such as `Result<(), E>`",
+            )
+            .into());
+        };
+        let entercase = quote! {
+            let _case = match #caseinfo.enter_selected() {
+                Some(guard) => guard,
+                None => return #skipped,
+            };
+        };
+
+        let casefn = match expect_error {
+            None if returns_unit(tyret) => quote! {
+                fn #casefnname() #tyret {
+                    #entercase
+                    #cmd
+                    #implname #turbofish ( #( #args ),* )
+                }
+            },
+            // An error is reported after the case is left, so the guard must be told of it:
+            None => quote! {
+                fn #casefnname() #tyret {
+                    #entercase
+                    #cmd
+                    let result = #implname #turbofish ( #( #args ),* );
+                    if result.is_err() {
+                        _case.fail();
+                    }
+                    result
+                }
+            },
+            Some(expected) => {
+                let casedir = casedir.display().to_string();
+                let call = quote! { #implname #turbofish ( #( #args ),* ) };
+                let result = if returns_unit(tyret) {
+                    quote! {
+                        #call;
+                        Ok(())
+                    }
+                } else {
+                    quote! {
+                        #call.map(|_| ()).map_err(|e| e.to_string())
+                    }
+                };
+                quote! {
+                    fn #casefnname() {
+                        fn run() -> ::std::result::Result<(), ::std::string::String> {
+                            #result
+                        }
+
+                        #entercase
+                        match run() {
+                            Ok(()) => panic!("case {} succeeded but was expected to fail", #casedir),
+                            Err(error) => assert!(
+                                error.contains(#expected),
+                                "case {} failed with error {:?}, which does not contain the expected {:?}",
+                                #casedir,
+                                error,
+                                #expected,
+                            ),
+                        }
+                    }
+                }
+            }
+        };
         casefns.push(quote! {
             #testattr
-            fn #casefnname() #tyret {
-                #implname(
-                    #(
-                        <#argtypes>::try_from(
-                            &include_bytes!( #argpaths )[..]
-                        ).unwrap()
-                    ),*
-                )
-            }
+            #lintattr
+            #casefn
             #execdoctest
         });
     }
 
     Ok(quote! {
+        #inputs
         #implfn
 
         #( #casefns )*
     })
 }
+
+fn bench_vectors_result(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
+    use quote::quote;
+
+    let Expansion {
+        params,
+        implfn,
+        basename,
+        spanargs,
+        calls,
+        inputs,
+    } = Expansion::new(args, input)?;
+    let inputs = track_inputs(&inputs);
+
+    if params.bin.is_some() {
+        return Err(syn::Error::new(spanargs, "`bench_vectors` does not support `bin`").into());
+    }
+
+    let implname = &implfn.sig.ident;
+    let groupname = syn::Ident::new(&basename, spanargs);
+    let vis = &implfn.vis;
+
+    let argidents: Vec<syn::Ident> = implfn
+        .sig
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(i, _)| syn::Ident::new(&format!("arg{}", i), spanargs))
+        .collect();
+
+    // Measuring a case which is expected to fail is not meaningful:
+    let benches = calls
+        .into_iter()
+        .filter(|call| call.expect_error.is_none())
+        .map(|call| {
+            let CaseCall {
+                name,
+                turbofish,
+                args,
+                ..
+            } = call;
+
+            // Argument conversion happens in the setup closure so it is excluded from measurement:
+            quote! {
+                group.bench_function(#name, |b| {
+                    b.iter_batched(
+                        || ( #( #args, )* ),
+                        |( #( #argidents, )* )| #implname #turbofish ( #( #argidents ),* ),
+                        ::criterion::BatchSize::SmallInput,
+                    )
+                });
+            }
+        });
+
+    Ok(quote! {
+        #inputs
+        #implfn
+
+        #vis fn #groupname(c: &mut ::criterion::Criterion) {
+            let mut group = c.benchmark_group(#basename);
+            #( #benches )*
+            group.finish();
+        }
+    })
+}
+
+/// A parsed criterion function along with every call to generate from the corpus
+struct Expansion {
+    params: MacroParams,
+    /// The criterion function, renamed to `impl_<basename>`
+    implfn: syn::ItemFn,
+    basename: String,
+    spanargs: Span,
+    calls: Vec<CaseCall>,
+    /// Files read during expansion rather than embedded with `include_bytes!`
+    inputs: Vec<PathBuf>,
+}
+
+/// A single call of the criterion function for one case, implementation, and params combination
+struct CaseCall {
+    /// The case name with any implementation and params suffixes, ie `alpha_Soft_level_6`
+    name: String,
+    /// The case name without suffixes
+    casename: String,
+    casedir: PathBuf,
+    /// Whether the case directory name is valid UTF-8
+    utf8: bool,
+    /// Whether `name` includes a user-chosen implementation name which may not be snake case
+    nonsnake: bool,
+    turbofish: TokenStream,
+    /// An expression for each argument converting case file contents or giving a params value
+    args: Vec<TokenStream>,
+    /// For a case expected to fail, the text its error must contain, which may be empty
+    expect_error: Option<String>,
+    /// A `CaseMeta` expression for the case metadata
+    meta: TokenStream,
+    /// A statement running and checking the `bin` command, if any, before the criterion function
+    cmd: TokenStream,
+}
+
+impl Expansion {
+    fn new(args: TokenStream, input: TokenStream) -> Result<Self> {
+        use quote::quote;
+        use syn::spanned::Spanned;
+        use test_vectors_core::fnargs::{is_case_temp_dir, is_tree, parse_fn_args};
+        use test_vectors_core::listdir::{
+            case_chain, list_case_entries, list_dir, list_tree, resolve_case_file, EXTENDS_FILE,
+        };
+        use test_vectors_core::manifest::Manifest;
+        use test_vectors_core::meta::{Meta, META_FILES};
+        use test_vectors_core::params::{CMD_FILES, EXPECT_ERROR_FILE};
+
+        let spanargs = args.span();
+        let spaninput = input.span();
+        let params = MacroParams::parse(args)?;
+        let mut implfn: syn::ItemFn = syn::parse2(input)?;
+
+        // Save the impl fn name and rename it:
+        let basename = implfn.sig.ident.to_string();
+        implfn.sig.ident = syn::Ident::new(&format!("impl_{}", &basename), implfn.sig.ident.span());
+
+        let (argnames, argtypes) =
+            parse_fn_args(&implfn.sig).map_err(|s| syn::Error::new(spaninput, s))?;
+        let variants = type_variants(&implfn.sig, &params.types)?;
+        let combos = param_combinations(&argnames, &params.params, spanargs)?;
+        let cases = list_dir(&params.dir, params.sort)
+            .map_err(|e| syn::Error::new(spanargs, e.to_string()))?;
+        params
+            .check_case_count(cases.len())
+            .map_err(|s| syn::Error::new(spanargs, s))?;
+
+        let mut inputs = vec![];
+        let manifest = params
+            .manifest
+            .as_ref()
+            .map(|path| {
+                inputs.push(path.clone());
+                let manifest = Manifest::read(path)
+                    .map_err(|e| syn::Error::new(spanargs, format!("manifest {}", e)))?;
+                // Guard against a truncated corpus as well as altered files:
+                for relpath in manifest.paths() {
+                    if !params.dir.join(relpath).is_file() {
+                        return Err(syn::Error::new(
+                            spanargs,
+                            format!("manifest {:?} lists {} which does not exist", path, relpath),
+                        ));
+                    }
+                }
+                Ok(manifest)
+            })
+            .transpose()?;
+
+        // Arguments bound by `params(...)` take literal values, and `CaseTempDir` arguments take a
+        // copy of the case directory, rather than case files:
+        let tempdir_args: Vec<&String> = argnames
+            .iter()
+            .zip(&argtypes)
+            .filter(|(_, ty)| is_case_temp_dir(ty))
+            .map(|(name, _)| name)
+            .collect();
+        let is_file_arg = |name: &String| {
+            argnames.contains(name)
+                && !params.params.iter().any(|(p, _)| p == name)
+                && !tempdir_args.contains(&name)
+        };
+
+        let mut calls = vec![];
+        for case in cases {
+            let casedir = params.dir.join(&case.dirname);
+
+            inputs.extend(
+                META_FILES
+                    .iter()
+                    .map(|name| casedir.join(name))
+                    .filter(|path| path.is_file()),
+            );
+            let meta = Meta::read(&casedir).map_err(|e| syn::Error::new(spanargs, e))?;
+            if !params.tags.matches(&meta.tags) {
+                continue;
+            }
+
+            // A criterion with a copy of the case directory may use any of its entries:
+            if params.strict && tempdir_args.is_empty() {
+                let unused: Vec<String> = list_case_entries(&casedir)
+                    .map_err(|e| syn::Error::new(spanargs, e.to_string()))?
+                    .into_iter()
+                    .filter(|name| !is_file_arg(name) && !params.is_ignored(name))
+                    .collect();
+
+                if !unused.is_empty() {
+                    return Err(syn::Error::new(
+                        spanargs,
+                        format!(
+                            "strict: case directory {:?} contains entries not used by any argument: {}",
+                            casedir,
+                            unused.join(", "),
+                        ),
+                    )
+                    .into());
+                }
+            }
+
+            let markerpath = casedir.join(EXPECT_ERROR_FILE);
+            let expect_error = if markerpath.is_file() {
+                inputs.push(markerpath.clone());
+                let expected = std::fs::read_to_string(&markerpath)
+                    .map_err(|e| syn::Error::new(spanargs, format!("{:?}: {}", markerpath, e)))?;
+                Some(expected.trim().to_string())
+            } else if params.expect_error {
+                Some(String::new())
+            } else {
+                None
+            };
+            if expect_error.is_some() && params.bin.is_some() {
+                return Err(syn::Error::new(
+                    spanargs,
+                    format!(
+                        "case directory {:?} is expected to fail, which `bin` does not support; use a `status` file instead",
+                        casedir,
+                    ),
+                )
+                .into());
+            }
+
+            // Embed a resolved case file after checking it against any manifest:
+            let embed = |argpath: PathBuf| -> syn::Result<TokenStream> {
+                if let (Some(manifest), Some(path)) = (&manifest, &params.manifest) {
+                    let contents = std::fs::read(&argpath)
+                        .map_err(|e| syn::Error::new(spanargs, format!("{:?}: {}", argpath, e)))?;
+                    let relpath = argpath
+                        .strip_prefix(&params.dir)
+                        .unwrap_or(&argpath)
+                        .to_string_lossy()
+                        .replace(std::path::MAIN_SEPARATOR, "/");
+                    manifest.verify(&relpath, &contents).map_err(|e| {
+                        syn::Error::new(spanargs, format!("manifest {:?}: {}", path, e))
+                    })?;
+                }
+                if let Some(argpath) = argpath.to_str() {
+                    Ok(quote! { include_bytes!( #argpath ) })
+                } else {
+                    // A non-UTF-8 path cannot be spelled in a string literal for
+                    // `include_bytes!`, so embed the contents directly:
+                    let bytes = std::fs::read(&argpath)
+                        .map_err(|e| syn::Error::new(spanargs, format!("{:?}: {}", argpath, e)))?;
+                    let lit = syn::LitByteStr::new(&bytes, spanargs);
+                    Ok(quote! { #lit })
+                }
+            };
+            // Case files are resolved through any chain of base cases:
+            inputs.extend(
+                case_chain(&casedir)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|dir| dir.join(EXTENDS_FILE))
+                    .filter(|path| path.is_file()),
+            );
+            let resolve = |arg: &str| {
+                resolve_case_file(&params.dir, &casedir, arg)
+                    .map_err(|e| syn::Error::new(spanargs, e.to_string()))
+            };
+
+            // The conversion of each file argument, from the bytes of a file with `FromVector`, or
+            // for a `Tree` argument, from the files beneath a directory:
+            let conversions = argnames
+                .iter()
+                .zip(&argtypes)
+                .map(|(arg, argtype)| {
+                    if !is_file_arg(arg) {
+                        return Ok(None);
+                    }
+                    // A missing file is left for `include_bytes!` to report, and other mismatches
+                    // for `compile_error!`, since tests which are never compiled, such as in doc
+                    // examples, need not have their files:
+                    let argpath = resolve(arg)?.unwrap_or_else(|| casedir.join(arg));
+                    let mismatch = |message: String| {
+                        Ok(Some(quote::quote_spanned! { spanargs => compile_error!(#message) }))
+                    };
+                    if is_tree(argtype) != argpath.is_dir() {
+                        return mismatch(if argpath.is_dir() {
+                            format!(
+                                "{:?} is a directory, which only an argument of type `Tree` can take",
+                                argpath,
+                            )
+                        } else if argpath.exists() {
+                            format!(
+                                "`Tree` argument `{}` requires a directory, but {:?} is a file",
+                                arg, argpath,
+                            )
+                        } else {
+                            format!(
+                                "`Tree` argument `{}` requires a directory {:?}, which does not exist",
+                                arg, argpath,
+                            )
+                        });
+                    }
+                    if !argpath.is_dir() {
+                        let bytes = embed(argpath)?;
+                        return Ok(Some(quote! {
+                            <#argtype as ::test_vectors::FromVector>::from_vector(&#bytes[..])
+                        }));
+                    }
+
+                    let entries = list_tree(&argpath)
+                        .map_err(|e| syn::Error::new(spanargs, format!("{:?}: {}", argpath, e)))?
+                        .into_iter()
+                        .map(|relpath| {
+                            let bytes = embed(argpath.join(&relpath))?;
+                            Ok(quote! { (#relpath, &#bytes[..]) })
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    Ok(Some(quote! {
+                        <#argtype>::try_from(
+                            ::test_vectors::Tree::from_entries(&[ #( #entries ),* ])
+                        )
+                    }))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            // Each command file present sets the corresponding `Cmd` field:
+            let cmd = match &params.bin {
+                None => quote! {},
+                Some(bin) => {
+                    let mut setters = vec![];
+                    for file in CMD_FILES {
+                        if let Some(argpath) = resolve(file)? {
+                            let setter = syn::Ident::new(file, spanargs);
+                            let bytes = embed(argpath)?;
+                            setters.push(quote! { .#setter(#bytes) });
+                        }
+                    }
+                    let program = format!("CARGO_BIN_EXE_{}", bin);
+                    quote! {
+                        ::test_vectors::cmd::Cmd::new() #( #setters )* .assert(env!(#program));
+                    }
+                }
+            };
+
+            for ((tysuffix, turbofish), (paramsuffix, values)) in variants
+                .iter()
+                .flat_map(|v| combos.iter().map(move |c| (v, c)))
+            {
+                let args = conversions
+                    .iter()
+                    .zip(values)
+                    .map(|(conversion, value)| match (conversion, value) {
+                        // A conversion failure satisfies a case expected to fail:
+                        (Some(conversion), _) if expect_error.is_some() => Ok(quote! {
+                            match #conversion {
+                                Ok(arg) => arg,
+                                Err(e) => return Err(e.to_string()),
+                            }
+                        }),
+                        (Some(conversion), _) => Ok(quote! { #conversion.unwrap() }),
+                        (None, Some(value)) => Ok(quote! { #value }),
+                        (None, None) => {
+                            let casedir = casedir.to_str().ok_or_else(|| {
+                                syn::Error::new(
+                                    spanargs,
+                                    format!(
+                                        "case directory {:?} is not valid UTF-8, which `CaseTempDir` requires",
+                                        casedir,
+                                    ),
+                                )
+                            })?;
+                            Ok(quote! { ::test_vectors::CaseTempDir::new(#casedir).unwrap() })
+                        }
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+
+                calls.push(CaseCall {
+                    name: format!("{}{}{}", &case.name, tysuffix, paramsuffix),
+                    casename: case.name.clone(),
+                    casedir: casedir.clone(),
+                    utf8: case.is_utf8(),
+                    nonsnake: !tysuffix.is_empty(),
+                    turbofish: turbofish.clone(),
+                    args,
+                    expect_error: expect_error.clone(),
+                    meta: meta_tokens(&meta),
+                    cmd: cmd.clone(),
+                });
+            }
+        }
+
+        if calls.is_empty() && !params.tags.is_empty() && !params.allow_empty {
+            return Err(syn::Error::new(
+                spanargs,
+                format!(
+                    "tags = {:?} selects no cases in corpus directory {:?}; use `allow_empty = true` if this is intended",
+                    params.tags.to_string(),
+                    params.dir,
+                ),
+            )
+            .into());
+        }
+
+        Ok(Expansion {
+            params,
+            implfn,
+            basename,
+            spanargs,
+            calls,
+            inputs,
+        })
+    }
+}
+
+/// Produce a `CaseMeta` expression embedding `meta`
+fn meta_tokens(meta: &test_vectors_core::meta::Meta) -> TokenStream {
+    use quote::quote;
+
+    let description = match &meta.description {
+        Some(description) => quote! { Some(#description) },
+        None => quote! { None },
+    };
+    let tags = &meta.tags;
+    let references = &meta.references;
+    quote! {
+        ::test_vectors::CaseMeta::new(#description, &[ #( #tags ),* ], &[ #( #references ),* ])
+    }
+}
+
+/// Whether a criterion function returns `()`, so it can only fail by panicking
+fn returns_unit(output: &syn::ReturnType) -> bool {
+    match output {
+        syn::ReturnType::Default => true,
+        syn::ReturnType::Type(_, ty) => matches!(&**ty, syn::Type::Tuple(t) if t.elems.is_empty()),
+    }
+}
+
+/// Register each of `inputs` with the compiler, so that cargo rebuilds the crate when one changes
+///
+/// A path which is not valid UTF-8 cannot be spelled for `include_bytes!`, so it is not tracked.
+fn track_inputs(inputs: &[PathBuf]) -> TokenStream {
+    use quote::quote;
+
+    let paths = inputs.iter().filter_map(|path| path.to_str());
+    quote! {
+        #( const _: &[u8] = include_bytes!(#paths); )*
+    }
+}
+
+/// Whether `output` is a `Result`-like path type with `()` as its first type argument, such as
+/// `Result<(), E>` or `std::io::Result<()>`
+fn returns_unit_result(output: &syn::ReturnType) -> bool {
+    let syn::ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    let syn::Type::Path(path) = &**ty else {
+        return false;
+    };
+    let Some(syn::PathArguments::AngleBracketed(generics)) =
+        path.path.segments.last().map(|segment| &segment.arguments)
+    else {
+        return false;
+    };
+    matches!(
+        generics.args.first(),
+        Some(syn::GenericArgument::Type(syn::Type::Tuple(t))) if t.elems.is_empty()
+    )
+}
+
+/// Produce the test name suffix and turbofish for each implementation given by `types(...)`
+///
+/// Without `types`, there is a single variant with an empty suffix and no turbofish.
+fn type_variants(
+    sig: &syn::Signature,
+    types: &[(syn::Ident, syn::Type)],
+) -> syn::Result<Vec<(String, TokenStream)>> {
+    use quote::quote;
+
+    if types.is_empty() {
+        return Ok(vec![(String::new(), quote! {})]);
+    }
+
+    let typaramcount = sig.generics.type_params().count();
+    if typaramcount != 1 {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            format!(
+                "`types(...)` requires a criterion function with exactly one type parameter, found {}",
+                typaramcount,
+            ),
+        ));
+    }
+
+    Ok(types
+        .iter()
+        .map(|(name, ty)| (format!("_{}", name), quote! { ::<#ty> }))
+        .collect())
+}
+
+/// Produce the test name suffix and per-argument values for each combination of `params(...)`
+///
+/// The values are aligned with `argnames`, with `None` for arguments loaded from case files.
+/// Without `params`, there is a single combination with an empty suffix.
+fn param_combinations(
+    argnames: &[String],
+    params: &[(syn::Ident, Vec<syn::Expr>)],
+    span: Span,
+) -> syn::Result<Vec<(String, Vec<Option<syn::Expr>>)>> {
+    use quote::ToTokens;
+
+    let mut combos = vec![(String::new(), vec![None; argnames.len()])];
+    for (name, values) in params {
+        let argix = argnames.iter().position(|arg| name == arg).ok_or_else(|| {
+            syn::Error::new(
+                name.span(),
+                format!(
+                    "`params` names `{}` which is not a criterion function argument",
+                    name
+                ),
+            )
+        })?;
+        if values.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                format!("`params` gives no values for `{}`", name),
+            ));
+        }
+
+        let labels: Vec<String> = values
+            .iter()
+            .map(|value| {
+                let label = match value {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(s),
+                        ..
+                    }) => s.value(),
+                    other => other.to_token_stream().to_string(),
+                };
+                label
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect()
+            })
+            .collect();
+        // Distinct values whose labels collide would generate duplicate test functions:
+        for (ix, label) in labels.iter().enumerate() {
+            if let Some(prev) = labels[..ix].iter().position(|l| l == label) {
+                return Err(syn::Error::new_spanned(
+                    &values[ix],
+                    format!(
+                        "`params` values `{}` and `{}` for `{}` both produce the test name suffix `{}`",
+                        values[prev].to_token_stream(),
+                        values[ix].to_token_stream(),
+                        name,
+                        label,
+                    ),
+                ));
+            }
+        }
+
+        combos = combos
+            .into_iter()
+            .flat_map(|(suffix, args)| {
+                values.iter().zip(&labels).map(move |(value, label)| {
+                    let mut args = args.clone();
+                    args[argix] = Some(value.clone());
+                    (format!("{}_{}_{}", suffix, name, label), args)
+                })
+            })
+            .collect();
+    }
+    // Labels of different parameters can still run together into the same suffix:
+    for (ix, (suffix, _)) in combos.iter().enumerate() {
+        if combos[..ix].iter().any(|(prev, _)| prev == suffix) {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "`params` produces the test name suffix `{}` for two combinations of values",
+                    suffix
+                ),
+            ));
+        }
+    }
+    Ok(combos)
+}
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+use test_vectors_core::check::check_crate;
+
+/// Report problems in the crate's corpora, failing if there are any
+///
+/// Directories in `corpora` are expected to be used by some criterion function.
+pub(crate) fn check(corpora: &[&str]) -> Result<(), String> {
+    let crate_dir = crate::find_crate_dir()?;
+    let cwd = std::env::current_dir().map_err(|e| e.to_string())?;
+    let corpora: Vec<PathBuf> = corpora.iter().map(|dir| cwd.join(dir)).collect();
+
+    let report = check_crate(&crate_dir, &corpora).map_err(|e| e.to_string())?;
+    for finding in report.findings.iter() {
+        println!("{}", finding);
+    }
+    println!(
+        "checked {} criterion functions using {} corpora: {} problems",
+        report.criteria,
+        report.corpora,
+        report.findings.len(),
+    );
+
+    if report.findings.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("found {} problems", report.findings.len()))
+    }
+}
@@ -0,0 +1,92 @@
+//! Command line tools for test vector corpora
+//!
+//! Installed as a cargo subcommand, this runs as `cargo test-vectors <command> ...`.
+
+mod check;
+mod new;
+
+use std::path::{Path, PathBuf};
+use test_vectors::{fuzz, Corpus};
+use test_vectors_core::manifest::Manifest;
+
+const USAGE: &str = "\
+usage: cargo test-vectors <command> <args...>
+
+commands:
+  new <corpus dir> <case>
+      create a case directory with an empty file for every argument of the
+      criterion functions which use the corpus, unless a shared file provides it
+  check [<corpus dir>...]
+      report case directories with missing, unused, or misnamed entries and
+      corpora with too few cases, without compiling; each <corpus dir> given
+      is also reported if no criterion function uses it, but other unused
+      corpora are not found; test names are checked without the suffixes
+      added by types(...) and params(...), so collisions between them are
+      only reported when compiling
+  manifest <corpus dir> [<manifest name>]
+      regenerate the checksum manifest, SHA256SUMS by default, of every file in
+      the corpus case directories
+  fuzz-export <corpus dir> <arg> <fuzz corpus dir>
+      copy the <arg> file of each case into a flat fuzzing corpus directory
+  fuzz-import <artifact dir> <corpus dir> <arg>
+      create a case with an <arg> file from each fuzzing artifact";
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    // When run as `cargo test-vectors`, cargo passes the subcommand name first:
+    if args.first().map(String::as_str) == Some("test-vectors") {
+        args.remove(0);
+    }
+
+    if let Err(e) = run(&args) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let strs: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match strs.as_slice() {
+        ["new", corpus, case] => new::new_case(Path::new(corpus), case),
+        ["check", corpora @ ..] => check::check(corpora),
+        ["manifest", corpus, rest @ ..] if rest.len() <= 1 => {
+            let name = rest.first().copied().unwrap_or("SHA256SUMS");
+            let count = Manifest::regenerate(Path::new(corpus), name)
+                .map_err(|e| format!("{}: {}", corpus, e))?;
+            println!(
+                "listed {} files in {}",
+                count,
+                Path::new(corpus).join(name).display()
+            );
+            Ok(())
+        }
+        ["fuzz-export", corpus, arg, out] => {
+            let corpus = Corpus::open(corpus).map_err(|e| format!("{}: {}", corpus, e))?;
+            let count = fuzz::export_seeds(&corpus, arg, Path::new(out))
+                .map_err(|e| format!("{}: {}", out, e))?;
+            println!("exported {} seeds to {}", count, out);
+            Ok(())
+        }
+        ["fuzz-import", artifacts, corpus, arg] => {
+            let created = fuzz::import_artifacts(Path::new(artifacts), Path::new(corpus), arg)
+                .map_err(|e| e.to_string())?;
+            for casedir in created.iter() {
+                println!("created {}", casedir.display());
+            }
+            println!("imported {} cases into {}", created.len(), corpus);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// Find the crate containing the current directory
+fn find_crate_dir() -> Result<PathBuf, String> {
+    let cwd = std::env::current_dir().map_err(|e| e.to_string())?;
+    cwd.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("no Cargo.toml found in {} or its parents", cwd.display()))
+}
@@ -0,0 +1,48 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use test_vectors_core::listdir::resolve_case_file;
+use test_vectors_core::scan::scan_crate;
+
+/// Create `case` in `corpus` with a placeholder file for each argument the corpus criteria expect,
+/// or an empty directory for each `Tree` argument
+pub(crate) fn new_case(corpus: &Path, case: &str) -> Result<(), String> {
+    if case.is_empty() || !case.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(format!(
+            "case name {:?} must consist of identifier characters to produce valid test names",
+            case
+        ));
+    }
+
+    let crate_dir = crate::find_crate_dir()?;
+    let usages = scan_crate(&crate_dir).map_err(|e| e.to_string())?;
+    let usages: Vec<_> = usages.iter().filter(|u| u.uses_corpus(corpus)).collect();
+    if usages.is_empty() {
+        return Err(format!(
+            "no criterion function in {} uses corpus {}",
+            crate_dir.display(),
+            corpus.display(),
+        ));
+    }
+    let args: BTreeSet<&str> = usages.iter().flat_map(|u| u.file_args()).collect();
+    let is_tree = |arg: &str| usages.iter().any(|u| u.tree_args.iter().any(|t| t == arg));
+
+    let casedir = corpus.join(case);
+    std::fs::create_dir_all(&casedir).map_err(|e| format!("{}: {}", casedir.display(), e))?;
+    for arg in args {
+        let path = casedir.join(arg);
+        if path.exists() {
+            println!("exists  {}", path.display());
+        } else if let Some(shared) =
+            resolve_case_file(corpus, &casedir, arg).map_err(|e| e.to_string())?
+        {
+            println!("shared  {}", shared.display());
+        } else if is_tree(arg) {
+            std::fs::create_dir(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            println!("created {}/", path.display());
+        } else {
+            std::fs::write(&path, b"").map_err(|e| format!("{}: {}", path.display(), e))?;
+            println!("created {}", path.display());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use crate::Tree;
+use std::cell::{Cell, RefCell};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use test_vectors_core::tags::TagFilter;
+
+/// The environment variable naming a directory to write mismatched actual values into
+pub const ACTUAL_DIR_VAR: &str = "TEST_VECTORS_ACTUAL_DIR";
+
+/// The environment variable selecting cases to run by tag, ie `fast,!network`
+pub const TAGS_VAR: &str = "TEST_VECTORS_TAGS";
+
+thread_local! {
+    static CURRENT: RefCell<Option<CaseInfo>> = const { RefCell::new(None) };
+    // Temporary directories awaiting the outcome of the running case:
+    static TEMP_DIRS: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The case a [test_vectors](crate::test_vectors) generated test is running
+///
+/// Each generated test registers its case for the duration of the criterion function call, so
+/// helpers such as [assert_vector_eq](crate::assert_vector_eq) can refer to the case without it
+/// being passed around.
+#[derive(Clone, Debug)]
+pub struct CaseInfo {
+    name: &'static str,
+    corpus: &'static str,
+    dir: Option<&'static str>,
+    write_actual: bool,
+    meta: CaseMeta,
+}
+
+/// Metadata from a case's `meta.toml` or `meta.json`, read when the test was generated
+///
+/// Every field is empty for a case without a metadata file.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CaseMeta {
+    description: Option<&'static str>,
+    tags: &'static [&'static str],
+    references: &'static [&'static str],
+}
+
+impl CaseInfo {
+    /// Describe a case; used by generated code
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        corpus: &'static str,
+        dir: Option<&'static str>,
+        write_actual: bool,
+        meta: CaseMeta,
+    ) -> Self {
+        CaseInfo {
+            name,
+            corpus,
+            dir,
+            write_actual,
+            meta,
+        }
+    }
+
+    /// The case running on the current thread, if any
+    pub fn current() -> Option<CaseInfo> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Register this case as running on the current thread until the guard is dropped; used by
+    /// generated code
+    #[doc(hidden)]
+    pub fn enter(self) -> CaseGuard {
+        let previous = CURRENT.with(|current| current.replace(Some(self)));
+        CaseGuard {
+            previous,
+            failed: Cell::new(false),
+            tempdirs: TEMP_DIRS.with(|dirs| dirs.borrow().len()),
+        }
+    }
+
+    /// Register this case as running like [CaseInfo::enter] if [CaseInfo::is_selected], or
+    /// report that it is skipped; used by generated code
+    #[doc(hidden)]
+    pub fn enter_selected(self) -> Option<CaseGuard> {
+        use std::io::Write;
+
+        if self.is_selected() {
+            return Some(self.enter());
+        }
+        // Write directly to stderr, which libtest does not capture, so skips are always visible:
+        writeln!(
+            std::io::stderr(),
+            "test-vectors: skipping case {} of {}: tags [{}] are not selected by {}={}",
+            self.name,
+            self.corpus,
+            self.meta.tags.join(", "),
+            TAGS_VAR,
+            std::env::var(TAGS_VAR).unwrap_or_default(),
+        )
+        .ok();
+        None
+    }
+
+    /// Whether the [TAGS_VAR] environment variable selects this case by its metadata tags
+    ///
+    /// Every case is selected if the variable is unset or empty.
+    pub fn is_selected(&self) -> bool {
+        self.is_selected_by(&std::env::var(TAGS_VAR).unwrap_or_default())
+    }
+
+    /// Whether the tag filter `list`, in the form of [TAGS_VAR], selects this case
+    fn is_selected_by(&self, list: &str) -> bool {
+        TagFilter::parse(list).matches(self.meta.tags)
+    }
+
+    /// The case name, which is the case directory name unless that is not valid UTF-8
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// The case directory, or `None` if its name is not valid UTF-8
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.map(Path::new)
+    }
+
+    /// The case metadata
+    pub fn meta(&self) -> &CaseMeta {
+        &self.meta
+    }
+
+    /// Save a mismatched actual value for the case file `arg`, returning where it was written
+    ///
+    /// If the [ACTUAL_DIR_VAR] environment variable is set, the corpus tree is mirrored beneath
+    /// it as `<corpus>/<case>/<arg>.actual`. Otherwise, with the `write_actual` macro argument,
+    /// the value is written beside the case file as `<case dir>/<arg>.actual`. Nothing is written
+    /// if neither applies, or if `arg` is not a plain file name.
+    pub fn write_actual(&self, arg: &str, actual: &[u8]) -> std::io::Result<Option<PathBuf>> {
+        let Some(path) = self.actual_path(arg) else {
+            return Ok(None);
+        };
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, actual)?;
+        Ok(Some(path))
+    }
+
+    /// Save a mismatched actual tree for the case directory `arg` like [CaseInfo::write_actual],
+    /// as a directory named `<arg>.actual` which replaces any previously saved tree
+    pub fn write_actual_tree(&self, arg: &str, actual: &Tree) -> std::io::Result<Option<PathBuf>> {
+        let Some(path) = self.actual_path(arg) else {
+            return Ok(None);
+        };
+        if path.is_dir() {
+            std::fs::remove_dir_all(&path)?;
+        }
+        std::fs::create_dir_all(&path)?;
+        actual.write(&path)?;
+        Ok(Some(path))
+    }
+
+    /// Where to save an actual value for `arg`, if anywhere
+    fn actual_path(&self, arg: &str) -> Option<PathBuf> {
+        self.actual_path_in(std::env::var_os(ACTUAL_DIR_VAR).as_deref(), arg)
+    }
+
+    /// Where to save an actual value for `arg` given the value of [ACTUAL_DIR_VAR], if anywhere
+    fn actual_path_in(&self, actualdir: Option<&OsStr>, arg: &str) -> Option<PathBuf> {
+        if arg.is_empty() || arg.contains(['/', '\\']) || arg == "." || arg == ".." {
+            return None;
+        }
+        let filename = format!("{}.actual", arg);
+
+        match (actualdir, self.dir) {
+            (Some(actualdir), _) => Some(
+                Path::new(actualdir)
+                    .join(self.corpus)
+                    .join(self.name)
+                    .join(filename),
+            ),
+            (None, Some(dir)) if self.write_actual => Some(Path::new(dir).join(filename)),
+            _ => None,
+        }
+    }
+}
+
+impl CaseMeta {
+    /// Describe metadata; used by generated code
+    #[doc(hidden)]
+    pub const fn new(
+        description: Option<&'static str>,
+        tags: &'static [&'static str],
+        references: &'static [&'static str],
+    ) -> Self {
+        CaseMeta {
+            description,
+            tags,
+            references,
+        }
+    }
+
+    /// What the case exercises
+    pub fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+
+    /// Labels for selecting cases, such as `slow` or `edge-case`
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.tags
+    }
+
+    /// Whether the case has `tag`
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag)
+    }
+
+    /// Specification sections, issues, or other sources for the case
+    pub fn references(&self) -> &'static [&'static str] {
+        self.references
+    }
+}
+
+/// Restores the previously running case when dropped
+///
+/// If the case panicked or its criterion returned an error, its description and references are
+/// printed to explain the failure, and its temporary directories are kept for debugging.
+/// Otherwise they are removed.
+#[doc(hidden)]
+pub struct CaseGuard {
+    previous: Option<CaseInfo>,
+    failed: Cell<bool>,
+    tempdirs: usize,
+}
+
+impl CaseGuard {
+    /// Record that the criterion function returned an error; used by generated code
+    pub fn fail(&self) {
+        self.failed.set(true);
+    }
+}
+
+impl Drop for CaseGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        let case = CURRENT.with(|current| current.replace(previous));
+        let tempdirs = TEMP_DIRS.with(|dirs| {
+            let mut dirs = dirs.borrow_mut();
+            let start = self.tempdirs.min(dirs.len());
+            dirs.split_off(start)
+        });
+
+        if !self.failed.get() && !std::thread::panicking() {
+            for path in tempdirs {
+                std::fs::remove_dir_all(path).ok();
+            }
+            return;
+        }
+        if let Some(case) = case {
+            if let Some(description) = case.meta.description {
+                eprintln!("case {}: {}", case.name, description);
+            }
+            for reference in case.meta.references {
+                eprintln!("case {} reference: {}", case.name, reference);
+            }
+        }
+        for path in tempdirs {
+            eprintln!("case temporary directory kept at {}", path.display());
+        }
+    }
+}
+
+/// Leave the removal of the temporary directory `path` to the running case, if any, which removes
+/// it only if the case succeeds; otherwise return `path`
+pub(crate) fn defer_removal(path: PathBuf) -> Result<(), PathBuf> {
+    if CURRENT.with(|current| current.borrow().is_none()) {
+        return Err(path);
+    }
+    TEMP_DIRS.with(|dirs| dirs.borrow_mut().push(path));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
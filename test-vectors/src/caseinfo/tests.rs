@@ -0,0 +1,110 @@
+use super::{CaseInfo, CaseMeta};
+use std::ffi::OsStr;
+use std::io::Result;
+use std::path::PathBuf;
+use target_test_dir::test_with_dir;
+
+#[test]
+fn current_case_is_scoped() {
+    assert!(CaseInfo::current().is_none());
+    {
+        let _outer = CaseInfo::new("outer", "corpus", None, false, CaseMeta::default()).enter();
+        {
+            let _inner = CaseInfo::new("inner", "corpus", None, false, CaseMeta::default()).enter();
+            assert_eq!(CaseInfo::current().unwrap().name(), "inner");
+        }
+        assert_eq!(CaseInfo::current().unwrap().name(), "outer");
+    }
+    assert!(CaseInfo::current().is_none());
+}
+
+#[test]
+fn meta() {
+    const META: CaseMeta = CaseMeta::new(Some("empty payload"), &["fast"], &[]);
+
+    let _case = CaseInfo::new("alpha", "corpus", None, false, META).enter();
+    let case = CaseInfo::current().unwrap();
+    assert_eq!(case.meta().description(), Some("empty payload"));
+    assert!(case.meta().has_tag("fast"));
+    assert!(!case.meta().has_tag("slow"));
+    assert!(case.meta().references().is_empty());
+}
+
+#[test]
+fn select_by_tags() {
+    let fast = CaseInfo::new(
+        "fast",
+        "corpus",
+        None,
+        false,
+        CaseMeta::new(None, &["fast"], &[]),
+    );
+    let slow = CaseInfo::new(
+        "slow",
+        "corpus",
+        None,
+        false,
+        CaseMeta::new(None, &["slow"], &[]),
+    );
+
+    assert!(fast.is_selected_by("") && slow.is_selected_by(""));
+    assert!(fast.is_selected_by("!slow") && !slow.is_selected_by("!slow"));
+    assert!(fast.is_selected_by("fast,slow") && slow.is_selected_by("fast,slow"));
+}
+
+#[test_with_dir]
+fn write_actual(testdir: PathBuf) -> Result<()> {
+    let casedir = testdir.join("corpus/alpha");
+    std::fs::create_dir_all(&casedir)?;
+    let casedir: &'static str = Box::leak(casedir.display().to_string().into_boxed_str());
+
+    let case = CaseInfo::new("alpha", "corpus", Some(casedir), false, CaseMeta::default());
+    assert_eq!(case.write_actual("expected", b"out")?, None);
+
+    let case = CaseInfo::new("alpha", "corpus", Some(casedir), true, CaseMeta::default());
+    let path = case.write_actual("expected", b"out")?.unwrap();
+    assert_eq!(path, testdir.join("corpus/alpha/expected.actual"));
+    assert_eq!(std::fs::read(&path)?, b"out");
+    assert_eq!(case.write_actual("../escape", b"out")?, None);
+
+    // A saved tree replaces the previous one:
+    let mut tree = crate::Tree::new();
+    tree.insert("stale", "");
+    case.write_actual_tree("expected_tree", &tree)?;
+    let mut tree = crate::Tree::new();
+    tree.insert("sub/file", "contents");
+    let path = case.write_actual_tree("expected_tree", &tree)?.unwrap();
+    assert_eq!(path, testdir.join("corpus/alpha/expected_tree.actual"));
+    assert_eq!(crate::Tree::read(&path)?, tree);
+
+    Ok(())
+}
+
+#[test]
+fn actual_path_in_actual_dir() {
+    let actualdir = Some(OsStr::new("actual"));
+
+    // The actual directory takes precedence over `write_actual`:
+    for write_actual in [false, true] {
+        let case = CaseInfo::new(
+            "alpha",
+            "corpus",
+            Some("cases/alpha"),
+            write_actual,
+            CaseMeta::default(),
+        );
+        assert_eq!(
+            case.actual_path_in(actualdir, "expected"),
+            Some(PathBuf::from("actual/corpus/alpha/expected.actual"))
+        );
+        assert_eq!(case.actual_path_in(actualdir, "../escape"), None);
+    }
+
+    // A case directory which is not valid UTF-8 is still mirrored by name:
+    let case = CaseInfo::new("alpha", "corpus", None, true, CaseMeta::default());
+    assert_eq!(case.actual_path_in(None, "expected"), None);
+    assert_eq!(
+        case.actual_path_in(actualdir, "expected"),
+        Some(PathBuf::from("actual/corpus/alpha/expected.actual"))
+    );
+}
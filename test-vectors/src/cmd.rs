@@ -0,0 +1,239 @@
+//! Run command line programs with case files as their input and expected output
+//!
+//! A case for a command line program holds up to six files:
+//!
+//! - `args`: the command line arguments, split like a shell would, so `-o "two words"` passes two
+//!   arguments; without it the program is run without arguments
+//! - `env`: environment variables to set, in the `KEY=VALUE` format of [Env]
+//! - `stdin`: the standard input; without it standard input is empty
+//! - `stdout` and `stderr`: the expected standard output and standard error; either is left
+//!   unchecked if absent
+//! - `status`: the expected exit code; without it the program must exit with `0`
+//!
+//! The `bin` argument of [test_vectors](crate::test_vectors) runs a binary target of the crate
+//! with these files for every case. [Cmd] does the same for any program, such as with a
+//! [Corpus](crate::Corpus) opened at runtime.
+
+use crate::diff::assert_vector_eq;
+use crate::Env;
+use std::ffi::OsStr;
+use std::io::{Error, ErrorKind, Result, Write};
+use std::process::{Command, Output, Stdio};
+
+/// A program invocation and its expected results, from the contents of a case's files
+///
+/// # Example
+///
+/// ```
+/// use test_vectors::cmd::Cmd;
+///
+/// # #[cfg(unix)]
+/// Cmd::new()
+///     .args(b"-c 'tr a-z A-Z; exit 3'")
+///     .stdin(b"hello\n")
+///     .stdout(b"HELLO\n")
+///     .status(b"3\n")
+///     .assert("sh");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Cmd<'a> {
+    args: Option<&'a [u8]>,
+    env: Option<&'a [u8]>,
+    stdin: Option<&'a [u8]>,
+    stdout: Option<&'a [u8]>,
+    stderr: Option<&'a [u8]>,
+    status: Option<&'a [u8]>,
+}
+
+impl<'a> Cmd<'a> {
+    /// An invocation without arguments or input, which is expected to exit with `0`
+    pub fn new() -> Self {
+        Cmd::default()
+    }
+
+    /// Set the contents of the `args` file
+    pub fn args(mut self, contents: &'a [u8]) -> Self {
+        self.args = Some(contents);
+        self
+    }
+
+    /// Set the contents of the `env` file
+    pub fn env(mut self, contents: &'a [u8]) -> Self {
+        self.env = Some(contents);
+        self
+    }
+
+    /// Set the contents of the `stdin` file
+    pub fn stdin(mut self, contents: &'a [u8]) -> Self {
+        self.stdin = Some(contents);
+        self
+    }
+
+    /// Set the contents of the `stdout` file
+    pub fn stdout(mut self, contents: &'a [u8]) -> Self {
+        self.stdout = Some(contents);
+        self
+    }
+
+    /// Set the contents of the `stderr` file
+    pub fn stderr(mut self, contents: &'a [u8]) -> Self {
+        self.stderr = Some(contents);
+        self
+    }
+
+    /// Set the contents of the `status` file
+    pub fn status(mut self, contents: &'a [u8]) -> Self {
+        self.status = Some(contents);
+        self
+    }
+
+    /// Run `program` with the arguments, environment, and standard input, without checking its
+    /// results
+    pub fn run<P>(&self, program: P) -> Result<Output>
+    where
+        P: AsRef<OsStr>,
+    {
+        let args = match self.args {
+            Some(contents) => split_args(contents).map_err(|e| invalid_file("args", e))?,
+            None => vec![],
+        };
+        let env = match self.env {
+            Some(contents) => Env::try_from(contents).map_err(|e| invalid_file("env", e))?,
+            None => Env::default(),
+        };
+
+        let mut cmd = Command::new(program);
+        env.apply(&mut cmd);
+        let mut child = cmd
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Feed stdin from another thread so a program which writes before reading all of its
+        // input cannot deadlock against a full output pipe:
+        let mut pipe = child.stdin.take().unwrap();
+        let stdin = self.stdin.unwrap_or_default().to_vec();
+        let writer = std::thread::spawn(move || match pipe.write_all(&stdin) {
+            // A program need not read all of its input:
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+            other => other,
+        });
+        let output = child.wait_with_output()?;
+        writer.join().unwrap()?;
+        Ok(output)
+    }
+
+    /// Run `program`, then panic unless its standard output, standard error, and exit status
+    /// match the expected files
+    ///
+    /// Mismatched outputs are reported with [assert_vector_eq](crate::assert_vector_eq), so they
+    /// are saved as `stdout.actual` or `stderr.actual` in the same way.
+    #[track_caller]
+    pub fn assert<P>(&self, program: P) -> Output
+    where
+        P: AsRef<OsStr>,
+    {
+        let program = program.as_ref();
+        let output = self
+            .run(program)
+            .unwrap_or_else(|e| panic!("could not run {:?}: {}", program, e));
+
+        if let Some(expected) = self.stdout {
+            assert_vector_eq("stdout", expected, &output.stdout);
+        }
+        if let Some(expected) = self.stderr {
+            assert_vector_eq("stderr", expected, &output.stderr);
+        }
+
+        let expected = match self.status {
+            Some(contents) => {
+                parse_status(contents).unwrap_or_else(|e| panic!("{}", invalid_file("status", e)))
+            }
+            None => 0,
+        };
+        if output.status.code() != Some(expected) {
+            panic!(
+                "{:?} exited with {}, expected exit code {}; stderr:\n{}",
+                program,
+                output.status,
+                expected,
+                String::from_utf8_lossy(&output.stderr),
+            );
+        }
+        output
+    }
+}
+
+/// Split the contents of an `args` file into arguments like a POSIX shell
+///
+/// Arguments are separated by whitespace, including newlines. Within single quotes every
+/// character is literal, within double quotes `\` escapes `"` and `\`, and elsewhere `\` escapes
+/// any character. There is no variable, glob, or other expansion.
+pub fn split_args(contents: &[u8]) -> std::result::Result<Vec<String>, String> {
+    let text = std::str::from_utf8(contents).map_err(|e| e.to_string())?;
+
+    let mut args = vec![];
+    let mut current: Option<String> = None;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => args.extend(current.take()),
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err("unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => arg.push(c),
+                            Some(c) => {
+                                arg.push('\\');
+                                arg.push(c);
+                            }
+                            None => return Err("unterminated double quote".to_string()),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err("unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => current.get_or_insert_with(String::new).push(c),
+                None => return Err("trailing backslash".to_string()),
+            },
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Parse the contents of a `status` file, an exit code with optional surrounding whitespace
+fn parse_status(contents: &[u8]) -> std::result::Result<i32, String> {
+    std::str::from_utf8(contents)
+        .map_err(|e| e.to_string())?
+        .trim()
+        .parse()
+        .map_err(|e| format!("expected an exit code: {}", e))
+}
+
+fn invalid_file(name: &str, e: String) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("invalid `{}` file: {}", name, e),
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,78 @@
+use super::{split_args, Cmd};
+
+#[test]
+fn split_args_like_a_shell() {
+    let split = |text: &str| split_args(text.as_bytes());
+
+    assert!(split("").unwrap().is_empty());
+    assert_eq!(
+        split("  -v\n--out  file\n").unwrap(),
+        ["-v", "--out", "file"]
+    );
+    assert_eq!(
+        split("'two words' \"x\\\"y\"").unwrap(),
+        ["two words", "x\"y"]
+    );
+    assert_eq!(split("a\\ b c''d \"\"").unwrap(), ["a b", "cd", ""]);
+    assert_eq!(split("'$HOME\\n' \"\\n\"").unwrap(), ["$HOME\\n", "\\n"]);
+    assert!(split("'open").is_err());
+    assert!(split("\"open\\\"").is_err());
+    assert!(split("trailing\\").is_err());
+    assert!(split_args(b"\xff").is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn assert_matching_outputs() {
+    let output = Cmd::new()
+        .args(b"-c 'cat; echo oops >&2; exit 2'")
+        .stdin(b"input\n")
+        .stdout(b"input\n")
+        .stderr(b"oops\n")
+        .status(b"2\n")
+        .assert("sh");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[cfg(unix)]
+#[test]
+fn assert_defaults_to_success() {
+    Cmd::new().args(b"-c 'echo unchecked'").assert("sh");
+}
+
+#[cfg(unix)]
+#[test]
+#[should_panic(expected = "actual value does not match `stdout`")]
+fn assert_mismatched_stdout() {
+    Cmd::new()
+        .args(b"-c 'echo actual'")
+        .stdout(b"expected\n")
+        .assert("sh");
+}
+
+#[cfg(unix)]
+#[test]
+#[should_panic(expected = "expected exit code 0; stderr:\nfailed\n")]
+fn assert_mismatched_status() {
+    Cmd::new()
+        .args(b"-c 'echo failed >&2; exit 1'")
+        .assert("sh");
+}
+
+#[cfg(unix)]
+#[test]
+fn run_with_unread_stdin() {
+    let stdin = vec![b'x'; 1 << 20];
+    let output = Cmd::new().args(b"-c true").stdin(&stdin).run("sh").unwrap();
+    assert!(output.status.success());
+}
+
+#[cfg(unix)]
+#[test]
+fn run_with_env() {
+    Cmd::new()
+        .args(b"-c 'printf %s \"$GREETING\"'")
+        .env(b"GREETING=hello world\n")
+        .stdout(b"hello world")
+        .assert("sh");
+}
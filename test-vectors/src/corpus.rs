@@ -0,0 +1,131 @@
+use crate::{FromVector, Tree};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use test_vectors_core::listdir::{list_dir, resolve_case_file, SortOrder};
+
+/// A corpus directory opened at runtime
+///
+/// This discovers cases with exactly the same rules as the [test_vectors](crate::test_vectors)
+/// macro, for use where compile-time generation does not fit, such as build scripts, fuzz
+/// harnesses, or custom test harnesses.
+///
+/// # Example
+///
+/// ```
+/// use test_vectors::{Corpus, Utf8String};
+///
+/// let corpus = Corpus::open("test-data/example2")?;
+/// for case in corpus.cases() {
+///     let input: Utf8String = case.load("input")?;
+///     let underscores = case.file("underscores")?;
+///     assert_eq!(input.len(), underscores.len(), "case {}", case.name());
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Corpus {
+    dir: PathBuf,
+    cases: Vec<Case>,
+}
+
+/// A case directory within a [Corpus]
+#[derive(Debug)]
+pub struct Case {
+    name: String,
+    path: PathBuf,
+}
+
+impl Corpus {
+    /// Open the corpus directory `dir`, listing cases in [SortOrder::Lexical] order
+    ///
+    /// Unlike the macro, a relative `dir` is resolved against the current directory rather than
+    /// `CARGO_MANIFEST_DIR`.
+    pub fn open<P>(dir: P) -> std::io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Corpus::open_sorted(dir, SortOrder::default())
+    }
+
+    /// Open the corpus directory `dir`, listing cases in `order`
+    pub fn open_sorted<P>(dir: P, order: SortOrder) -> std::io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref().to_path_buf();
+        let cases = list_dir(&dir, order)?
+            .into_iter()
+            .map(|casedir| Case {
+                path: dir.join(&casedir.dirname),
+                name: casedir.name,
+            })
+            .collect();
+
+        Ok(Corpus { dir, cases })
+    }
+
+    /// The corpus directory path
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The cases in this corpus, in sorted order
+    pub fn cases(&self) -> &[Case] {
+        &self.cases
+    }
+}
+
+impl Case {
+    /// The case name, which is the case directory name or its escaped form if not valid UTF-8
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The case directory path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the contents of the file `name` for this case
+    ///
+    /// As with the macro, a file missing from the case directory falls back to the base cases it
+    /// extends, then to a shared file in the corpus `_shared` directory, then to a file at the top
+    /// level of the corpus.
+    pub fn file(&self, name: &str) -> std::io::Result<Vec<u8>> {
+        let corpus = self.path.parent().unwrap_or(&self.path);
+        let path =
+            resolve_case_file(corpus, &self.path, name)?.unwrap_or_else(|| self.path.join(name));
+        std::fs::read(path)
+    }
+
+    /// Read the file `name` for this case, with the same fallbacks as [Case::file], and convert it
+    /// with [FromVector] into an owned type such as `Vec<u8>` or [Utf8String](crate::Utf8String)
+    ///
+    /// A conversion failure is an error of kind [InvalidData](std::io::ErrorKind::InvalidData).
+    /// To convert into a type borrowing from the contents, such as [Utf8Str](crate::Utf8Str),
+    /// read them with [Case::file] and call [FromVector::from_vector] on the buffer.
+    pub fn load<T>(&self, name: &str) -> std::io::Result<T>
+    where
+        T: for<'a> FromVector<'a>,
+        for<'a> <T as FromVector<'a>>::Error: Display,
+    {
+        let contents = self.file(name)?;
+        T::from_vector(&contents).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {}", self.path.join(name).display(), e),
+            )
+        })
+    }
+
+    /// Snapshot the directory `name` for this case, with the same fallbacks as [Case::file]
+    pub fn tree(&self, name: &str) -> std::io::Result<Tree> {
+        let corpus = self.path.parent().unwrap_or(&self.path);
+        let path =
+            resolve_case_file(corpus, &self.path, name)?.unwrap_or_else(|| self.path.join(name));
+        Tree::read(path)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,83 @@
+use super::Corpus;
+use crate::{SortOrder, Utf8String};
+use std::io::{ErrorKind, Result};
+use std::path::PathBuf;
+use target_test_dir::test_with_dir;
+
+#[test_with_dir]
+fn sort_order(testdir: PathBuf) -> Result<()> {
+    for case in ["case_10", "case_2", "case_1"] {
+        std::fs::create_dir(testdir.join(case))?;
+    }
+    std::fs::write(testdir.join("README.md"), "not a case")?;
+
+    let names = |order| -> Result<Vec<String>> {
+        let corpus = Corpus::open_sorted(&testdir, order)?;
+        Ok(corpus
+            .cases()
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect())
+    };
+    assert_eq!(names(SortOrder::Lexical)?, ["case_1", "case_10", "case_2"]);
+    assert_eq!(names(SortOrder::Natural)?, ["case_1", "case_2", "case_10"]);
+    Ok(())
+}
+
+#[test_with_dir]
+fn shared_fallback(testdir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(testdir.join("_shared"))?;
+    std::fs::create_dir_all(testdir.join("alpha"))?;
+    std::fs::create_dir_all(testdir.join("beta"))?;
+    std::fs::write(testdir.join("_shared/key"), "shared")?;
+    std::fs::write(testdir.join("beta/key"), "beta")?;
+
+    let corpus = Corpus::open(&testdir)?;
+    let keys: Vec<(&str, Vec<u8>)> = corpus
+        .cases()
+        .iter()
+        .map(|case| Ok((case.name(), case.file("key")?)))
+        .collect::<Result<_>>()?;
+    assert_eq!(
+        keys,
+        [("alpha", b"shared".to_vec()), ("beta", b"beta".to_vec())]
+    );
+    Ok(())
+}
+
+#[test_with_dir]
+fn missing_and_invalid_files(testdir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(testdir.join("alpha"))?;
+    std::fs::write(testdir.join("alpha/binary"), b"\xff")?;
+
+    let corpus = Corpus::open(&testdir)?;
+    let case = &corpus.cases()[0];
+    assert_eq!(
+        case.file("missing").unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+
+    let e = case.load::<Utf8String>("binary").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidData);
+    assert!(e.to_string().contains("binary"), "{}", e);
+    let e = case.load::<String>("binary").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidData);
+    Ok(())
+}
+
+#[test_with_dir]
+fn load_owned_types(testdir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(testdir.join("alpha"))?;
+    std::fs::write(testdir.join("alpha/text"), "hello")?;
+
+    let corpus = Corpus::open(&testdir)?;
+    let case = &corpus.cases()[0];
+    assert_eq!(case.load::<String>("text")?, "hello");
+    assert_eq!(case.load::<Vec<u8>>("text")?, b"hello");
+    assert_eq!(case.load::<[u8; 5]>("text")?, *b"hello");
+    assert_eq!(
+        case.load::<[u8; 4]>("text").unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::FromVector;
+use serde::de::DeserializeOwned;
+use std::ops::Deref;
+
+/// Convenience type for tests with tables of records
+///
+/// A `Csv<T>` parses a case file as CSV with a header row, deserializing each following row into a
+/// `T` with [serde](https://docs.rs/serde), where fields are matched to struct fields by header
+/// name. It dereferences to `[T]` and iterates over the rows. It is available with the `csv`
+/// feature.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+/// use test_vectors::Csv;
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Sum {
+///     a: u32,
+///     b: u32,
+///     total: u32,
+/// }
+///
+/// let sums = Csv::<Sum>::try_from(&b"a,b,total\n1,2,3\n10,20,30\n"[..]).unwrap();
+/// for sum in &sums {
+///     assert_eq!(sum.a + sum.b, sum.total);
+/// }
+/// assert_eq!(sums.len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Csv<T> {
+    rows: Vec<T>,
+}
+
+impl<T> Csv<T> {
+    /// The deserialized rows, excluding the header
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    /// Take ownership of the deserialized rows
+    pub fn into_rows(self) -> Vec<T> {
+        self.rows
+    }
+}
+
+impl<T> Deref for Csv<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.rows
+    }
+}
+
+impl<T> TryFrom<&[u8]> for Csv<T>
+where
+    T: DeserializeOwned,
+{
+    type Error = csv::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Csv<T>, csv::Error> {
+        let rows = csv::Reader::from_reader(bytes)
+            .deserialize()
+            .collect::<Result<_, _>>()?;
+        Ok(Csv { rows })
+    }
+}
+
+impl<T> FromVector<'_> for Csv<T>
+where
+    T: DeserializeOwned,
+{
+    type Error = csv::Error;
+
+    fn from_vector(bytes: &[u8]) -> Result<Csv<T>, csv::Error> {
+        Csv::try_from(bytes)
+    }
+}
+
+impl<T> IntoIterator for Csv<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Csv<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,32 @@
+use super::Csv;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Row {
+    name: String,
+    count: u32,
+}
+
+#[test]
+fn rows_by_header() {
+    let csv = Csv::<Row>::try_from(&b"count,name\n3,three\n1,\"one, only\"\n"[..]).unwrap();
+    assert_eq!(
+        csv.into_rows(),
+        [
+            Row {
+                name: "three".to_string(),
+                count: 3
+            },
+            Row {
+                name: "one, only".to_string(),
+                count: 1
+            },
+        ]
+    );
+}
+
+#[test]
+fn invalid_field() {
+    let err = Csv::<Row>::try_from(&b"name,count\nthree,many\n"[..]).unwrap_err();
+    assert!(err.to_string().contains("line: 2"), "{}", err);
+}
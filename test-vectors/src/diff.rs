@@ -0,0 +1,314 @@
+//! Readable differences between expected and actual vector contents
+//!
+//! [crate::assert_vector_eq] uses [diff] to explain a mismatch. Contents which are both valid
+//! UTF-8 are compared as a unified line diff, and anything else as a hexdump with offsets. Long
+//! runs of equal content are elided, so a single changed byte in a large output produces a short
+//! report. [tree_diff] compares directory snapshots for [crate::assert_tree_eq], listing added,
+//! removed, and changed paths with a [diff] of each changed file.
+
+use crate::{CaseInfo, Tree};
+use std::fmt::Write;
+
+/// Lines of equal content shown around each change
+const CONTEXT: usize = 3;
+
+/// Bytes per hexdump row
+const HEX_WIDTH: usize = 16;
+
+/// The largest line comparison table computed before treating the lines as wholly replaced
+const MAX_TABLE_CELLS: usize = 1 << 22;
+
+/// Changed lines reported before the remainder of a diff is elided
+const MAX_CHANGED_LINES: usize = 200;
+
+/// Describe how `actual` differs from `expected`, or `None` if they are equal
+pub fn diff(expected: &[u8], actual: &[u8]) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let mut out = format!(
+        "expected {} bytes, actual {} bytes\n",
+        expected.len(),
+        actual.len()
+    );
+    match (std::str::from_utf8(expected), std::str::from_utf8(actual)) {
+        (Ok(expected), Ok(actual)) => line_diff(expected, actual, &mut out),
+        _ => hex_diff(expected, actual, &mut out),
+    }
+    Some(out)
+}
+
+/// Panic with a [diff] if `actual` differs from `expected`; used by [crate::assert_vector_eq]
+///
+/// Within a generated test, the actual value is first saved with [CaseInfo::write_actual].
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_vector_eq<E, A>(name: &str, expected: &E, actual: &A)
+where
+    E: AsRef<[u8]> + ?Sized,
+    A: AsRef<[u8]> + ?Sized,
+{
+    if let Some(diff) = diff(expected.as_ref(), actual.as_ref()) {
+        let saved = match CaseInfo::current().map(|case| case.write_actual(name, actual.as_ref())) {
+            Some(Ok(Some(path))) => format!("actual value written to {}\n", path.display()),
+            Some(Err(e)) => format!("could not write actual value: {}\n", e),
+            _ => String::new(),
+        };
+        panic!("actual value does not match `{}`: {}{}", name, diff, saved);
+    }
+}
+
+/// Describe how the `actual` tree differs from `expected`, or `None` if they are equal
+///
+/// Each path only in `actual` is reported as added, each path only in `expected` as removed, and
+/// each path in both with different contents as changed, followed by its indented [diff].
+pub fn tree_diff(expected: &Tree, actual: &Tree) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let mut out = format!(
+        "expected {} files, actual {} files\n",
+        expected.len(),
+        actual.len()
+    );
+    let mut paths: Vec<&str> = expected
+        .iter()
+        .chain(actual.iter())
+        .map(|(p, _)| p)
+        .collect();
+    paths.sort_unstable();
+    paths.dedup();
+    for path in paths {
+        match (expected.get(path), actual.get(path)) {
+            (Some(_), None) => writeln!(out, "removed {}", path).unwrap(),
+            (None, Some(_)) => writeln!(out, "added   {}", path).unwrap(),
+            (Some(e), Some(a)) => {
+                if let Some(diff) = diff(e, a) {
+                    writeln!(out, "changed {}", path).unwrap();
+                    for line in diff.lines() {
+                        writeln!(out, "    {}", line).unwrap();
+                    }
+                }
+            }
+            (None, None) => unreachable!("path is in neither tree"),
+        }
+    }
+    Some(out)
+}
+
+/// Panic with a [tree_diff] if `actual` differs from `expected`; used by
+/// [crate::assert_tree_eq]
+///
+/// Within a generated test, the actual tree is first saved with [CaseInfo::write_actual_tree].
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_tree_eq(name: &str, expected: &Tree, actual: &Tree) {
+    if let Some(diff) = tree_diff(expected, actual) {
+        let saved = match CaseInfo::current().map(|case| case.write_actual_tree(name, actual)) {
+            Some(Ok(Some(path))) => format!("actual tree written to {}\n", path.display()),
+            Some(Err(e)) => format!("could not write actual tree: {}\n", e),
+            _ => String::new(),
+        };
+        panic!("actual tree does not match `{}`: {}{}", name, diff, saved);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+fn line_diff(expected: &str, actual: &str, out: &mut String) {
+    let a: Vec<&str> = expected.split_inclusive('\n').collect();
+    let b: Vec<&str> = actual.split_inclusive('\n').collect();
+    let ops = edit_script(&a, &b);
+
+    out.push_str("--- expected\n+++ actual\n");
+    let (mut i, mut j) = (0, 0);
+    let mut positions = Vec::with_capacity(ops.len());
+    for op in ops.iter() {
+        positions.push((i, j));
+        match op {
+            Op::Equal => {
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+
+    let mut changed = 0;
+    for (start, end) in hunks(&ops, CONTEXT) {
+        let (i, j) = positions[start];
+        let count = |skip: Op| ops[start..end].iter().filter(|op| **op != skip).count();
+        writeln!(
+            out,
+            "@@ -{},{} +{},{} @@",
+            i + 1,
+            count(Op::Insert),
+            j + 1,
+            count(Op::Delete)
+        )
+        .unwrap();
+
+        for (op, &(i, j)) in ops[start..end].iter().zip(&positions[start..end]) {
+            let (sign, line) = match op {
+                Op::Equal => (' ', a[i]),
+                Op::Delete => ('-', a[i]),
+                Op::Insert => ('+', b[j]),
+            };
+            match line.strip_suffix('\n') {
+                Some(line) => writeln!(out, "{}{}", sign, line).unwrap(),
+                None => writeln!(out, "{}{}\n\\ No newline at end of file", sign, line).unwrap(),
+            }
+            if *op != Op::Equal {
+                changed += 1;
+                if changed == MAX_CHANGED_LINES {
+                    out.push_str("... remaining differences elided ...\n");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Find a shortest sequence of line edits from `a` to `b`
+fn edit_script(a: &[&str], b: &[&str]) -> Vec<Op> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (am, bm) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut ops = vec![Op::Equal; prefix];
+    if (am.len() + 1).saturating_mul(bm.len() + 1) > MAX_TABLE_CELLS {
+        ops.extend(std::iter::repeat_n(Op::Delete, am.len()));
+        ops.extend(std::iter::repeat_n(Op::Insert, bm.len()));
+    } else {
+        // `lcs[i][j]` is the longest common subsequence length of `am[i..]` and `bm[j..]`:
+        let width = bm.len() + 1;
+        let mut lcs = vec![0u32; (am.len() + 1) * width];
+        for i in (0..am.len()).rev() {
+            for j in (0..bm.len()).rev() {
+                lcs[i * width + j] = if am[i] == bm[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < am.len() || j < bm.len() {
+            if i < am.len() && j < bm.len() && am[i] == bm[j] {
+                ops.push(Op::Equal);
+                i += 1;
+                j += 1;
+            } else if j == bm.len()
+                || (i < am.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+            {
+                ops.push(Op::Delete);
+                i += 1;
+            } else {
+                ops.push(Op::Insert);
+                j += 1;
+            }
+        }
+    }
+    ops.extend(std::iter::repeat_n(Op::Equal, suffix));
+    ops
+}
+
+/// Group changes into `(start, end)` ranges of `ops` with up to `context` equal ops around them
+fn hunks(ops: &[Op], context: usize) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for (ix, _) in ops.iter().enumerate().filter(|(_, op)| **op != Op::Equal) {
+        let start = ix.saturating_sub(context);
+        let end = (ix + context + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    hunks
+}
+
+fn hex_diff(expected: &[u8], actual: &[u8], out: &mut String) {
+    let rows = expected.len().max(actual.len()).div_ceil(HEX_WIDTH);
+    let ops: Vec<Op> = (0..rows)
+        .map(|r| {
+            if row(expected, r) == row(actual, r) {
+                Op::Equal
+            } else {
+                Op::Delete
+            }
+        })
+        .collect();
+
+    out.push_str("--- expected\n+++ actual\n");
+    let mut shown = 0;
+    let mut changed = 0;
+    for (start, end) in hunks(&ops, 1) {
+        if start > shown {
+            writeln!(out, " ... {} equal rows ...", start - shown).unwrap();
+        }
+        for (r, op) in ops.iter().enumerate().take(end).skip(start) {
+            let offset = r * HEX_WIDTH;
+            if *op == Op::Equal {
+                hex_row(out, ' ', offset, row(expected, r).unwrap());
+                continue;
+            }
+            if let Some(bytes) = row(expected, r) {
+                hex_row(out, '-', offset, bytes);
+            }
+            if let Some(bytes) = row(actual, r) {
+                hex_row(out, '+', offset, bytes);
+            }
+            changed += 1;
+            if changed == MAX_CHANGED_LINES {
+                out.push_str("... remaining differences elided ...\n");
+                return;
+            }
+        }
+        shown = end;
+    }
+    if rows > shown {
+        writeln!(out, " ... {} equal rows ...", rows - shown).unwrap();
+    }
+}
+
+/// The `r`th hexdump row of `bytes`, if any
+fn row(bytes: &[u8], r: usize) -> Option<&[u8]> {
+    let start = r * HEX_WIDTH;
+    (start < bytes.len()).then(|| &bytes[start..bytes.len().min(start + HEX_WIDTH)])
+}
+
+fn hex_row(out: &mut String, sign: char, offset: usize, bytes: &[u8]) {
+    write!(out, "{}{:08x} ", sign, offset).unwrap();
+    for ix in 0..HEX_WIDTH {
+        match bytes.get(ix) {
+            Some(b) => write!(out, " {:02x}", b).unwrap(),
+            None => out.push_str("   "),
+        }
+    }
+    out.push_str("  |");
+    out.extend(bytes.iter().map(|&b| {
+        if b.is_ascii_graphic() || b == b' ' {
+            char::from(b)
+        } else {
+            '.'
+        }
+    }));
+    out.push_str("|\n");
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,119 @@
+use super::diff;
+
+#[test]
+fn equal_contents_have_no_diff() {
+    assert_eq!(diff(b"same", b"same"), None);
+}
+
+#[test]
+fn text_diff_is_unified() {
+    let expected = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+    let actual = "a\nb\nc\nd\ne\nF\ng\nh\ni\nj\nk\n";
+
+    assert_eq!(
+        diff(expected.as_bytes(), actual.as_bytes()).unwrap(),
+        "\
+expected 20 bytes, actual 22 bytes
+--- expected
++++ actual
+@@ -3,8 +3,9 @@
+ c
+ d
+ e
+-f
++F
+ g
+ h
+ i
+ j
++k
+"
+    );
+}
+
+#[test]
+fn text_diff_elides_distant_equal_lines() {
+    let expected: String = (0..1000).map(|n| format!("line {}\n", n)).collect();
+    let actual = expected
+        .replace("line 10\n", "line ten\n")
+        .replace("line 900\n", "line nine hundred\n");
+
+    let report = diff(expected.as_bytes(), actual.as_bytes()).unwrap();
+    assert!(report.contains("@@ -8,7 +8,7 @@\n"), "{}", report);
+    assert!(report.contains("@@ -898,7 +898,7 @@\n"), "{}", report);
+    assert!(!report.contains("line 500"), "{}", report);
+}
+
+#[test]
+fn text_diff_marks_missing_final_newline() {
+    let report = diff(b"end\n", b"end").unwrap();
+    assert!(
+        report.ends_with("-end\n+end\n\\ No newline at end of file\n"),
+        "{}",
+        report
+    );
+}
+
+#[test]
+fn binary_diff_is_hexdump() {
+    let expected: Vec<u8> = (0..=255).collect();
+    let mut actual = expected.clone();
+    actual[0x81] = 0xff;
+    actual.push(0);
+
+    assert_eq!(
+        diff(&expected, &actual).unwrap(),
+        "\
+expected 256 bytes, actual 257 bytes
+--- expected
++++ actual
+ ... 7 equal rows ...
+ 00000070  70 71 72 73 74 75 76 77 78 79 7a 7b 7c 7d 7e 7f  |pqrstuvwxyz{|}~.|
+-00000080  80 81 82 83 84 85 86 87 88 89 8a 8b 8c 8d 8e 8f  |................|
++00000080  80 ff 82 83 84 85 86 87 88 89 8a 8b 8c 8d 8e 8f  |................|
+ 00000090  90 91 92 93 94 95 96 97 98 99 9a 9b 9c 9d 9e 9f  |................|
+ ... 5 equal rows ...
+ 000000f0  f0 f1 f2 f3 f4 f5 f6 f7 f8 f9 fa fb fc fd fe ff  |................|
++00000100  00                                               |.|
+"
+    );
+}
+
+#[test]
+#[should_panic(expected = "actual value does not match `expected`")]
+fn assert_vector_eq_names_file() {
+    let expected: &[u8] = b"one\n";
+    crate::assert_vector_eq!(expected, "two\n");
+}
+
+#[test]
+fn tree_diff_lists_paths() {
+    use super::tree_diff;
+    use crate::Tree;
+
+    let mut expected = Tree::new();
+    expected.insert("same", "unchanged\n");
+    expected.insert("changed", "old\n");
+    expected.insert("removed", "");
+    let mut actual = expected.clone();
+    assert_eq!(tree_diff(&expected, &actual), None);
+
+    actual.insert("changed", "new\n");
+    actual.insert("dir/added", "");
+    actual.remove("removed");
+    assert_eq!(
+        tree_diff(&expected, &actual).unwrap(),
+        "\
+expected 3 files, actual 3 files
+changed changed
+    expected 4 bytes, actual 4 bytes
+    --- expected
+    +++ actual
+    @@ -1,1 +1,1 @@
+    -old
+    +new
+added   dir/added
+removed removed
+"
+    );
+}
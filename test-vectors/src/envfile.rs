@@ -0,0 +1,80 @@
+use crate::FromVector;
+use std::process::Command;
+
+/// Environment variables for a case, from an `env` case file of `KEY=VALUE` lines
+///
+/// Blank lines and lines starting with `#` are skipped, and each value is the rest of its line
+/// after the first `=`, without quoting or expansion. A criterion function receives the variables
+/// as an argument rather than having them set in its own process, because tests run concurrently
+/// in one process and would otherwise see each other's variables. They are typically passed to a
+/// child process with [Env::apply], which the `bin` mode of
+/// [test_vectors](crate::test_vectors) does automatically.
+///
+/// # Example
+///
+/// ```
+/// use test_vectors::Env;
+///
+/// let env = Env::try_from(&b"# locale for the case\nLANG=C\nGREETING=a=b\n"[..]).unwrap();
+/// assert_eq!(env.get("GREETING"), Some("a=b"));
+///
+/// let mut cmd = std::process::Command::new("printenv");
+/// env.apply(&mut cmd);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Env(Vec<(String, String)>);
+
+impl Env {
+    /// The value of `key`, if set
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The variables in the order they are listed
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Set every variable on `cmd`, in addition to those it inherits
+    pub fn apply(&self, cmd: &mut Command) {
+        cmd.envs(self.iter());
+    }
+}
+
+impl TryFrom<&[u8]> for Env {
+    type Error = String;
+
+    fn try_from(bytes: &[u8]) -> Result<Env, String> {
+        let text = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+
+        let mut vars = vec![];
+        for (ix, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once('=') {
+                Some((key, value)) if is_valid_key(key) && !value.contains('\0') => {
+                    vars.push((key.to_string(), value.to_string()));
+                }
+                _ => return Err(format!("line {}: expected `KEY=VALUE`", ix + 1)),
+            }
+        }
+        Ok(Env(vars))
+    }
+}
+
+impl FromVector<'_> for Env {
+    type Error = String;
+
+    fn from_vector(bytes: &[u8]) -> Result<Env, String> {
+        Env::try_from(bytes)
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && !key.contains(|c: char| c.is_whitespace() || c == '\0')
+}
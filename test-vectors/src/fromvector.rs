@@ -0,0 +1,94 @@
+use std::array::TryFromSliceError;
+use std::convert::Infallible;
+use std::str::Utf8Error;
+
+/// Conversion of a case file's contents into a criterion function argument
+///
+/// The [test_vectors](crate::test_vectors) macro converts each file argument with this trait, as
+/// does [Case::load](crate::Case::load) at runtime. It is implemented for byte types such as
+/// `&[u8]`, `Vec<u8>`, and `[u8; N]`, for `String` by decoding UTF-8, and for the wrapper types of
+/// this crate. A custom argument type implements it directly, and a foreign type which cannot
+/// implement it can be wrapped in a newtype.
+///
+/// The lifetime `'a` is that of the bytes converted from, which lets one signature serve both
+/// embedded and runtime-loaded vectors:
+///
+/// - Borrowing targets, such as `&[u8]`, [Utf8Str](crate::Utf8Str), or [Lines](crate::Lines),
+///   borrow from the embedded `'static` bytes in generated tests, or from a buffer read at
+///   runtime, so criterion functions should take them with an elided or generic lifetime such as
+///   `Utf8Str<'_>` rather than `'static`.
+/// - Owned targets, such as `Vec<u8>`, `String`, or [Utf8String](crate::Utf8String), copy the
+///   bytes and outlive them.
+///
+/// # Example
+///
+/// ```
+/// use test_vectors::{FromVector, Utf8Str, Utf8String};
+///
+/// fn check(input: Utf8Str<'_>, expected: Utf8String) {
+///     assert_eq!(input.replace(' ', "_"), *expected);
+/// }
+///
+/// let input = std::fs::read("test-data/example2/single_case/input")?;
+/// let expected = std::fs::read("test-data/example2/single_case/underscores")?;
+/// check(
+///     Utf8Str::from_vector(&input).unwrap(),
+///     Utf8String::from_vector(&expected).unwrap(),
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub trait FromVector<'a>: Sized {
+    /// The error when the contents are not a valid `Self`
+    type Error;
+
+    /// Convert the contents of a case file
+    fn from_vector(bytes: &'a [u8]) -> Result<Self, Self::Error>;
+}
+
+impl<'a> FromVector<'a> for &'a [u8] {
+    type Error = Infallible;
+
+    fn from_vector(bytes: &'a [u8]) -> Result<Self, Infallible> {
+        Ok(bytes)
+    }
+}
+
+impl FromVector<'_> for Vec<u8> {
+    type Error = Infallible;
+
+    fn from_vector(bytes: &[u8]) -> Result<Self, Infallible> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl FromVector<'_> for Box<[u8]> {
+    type Error = Infallible;
+
+    fn from_vector(bytes: &[u8]) -> Result<Self, Infallible> {
+        Ok(bytes.into())
+    }
+}
+
+impl<'a, const N: usize> FromVector<'a> for &'a [u8; N] {
+    type Error = TryFromSliceError;
+
+    fn from_vector(bytes: &'a [u8]) -> Result<Self, TryFromSliceError> {
+        bytes.try_into()
+    }
+}
+
+impl<const N: usize> FromVector<'_> for [u8; N] {
+    type Error = TryFromSliceError;
+
+    fn from_vector(bytes: &[u8]) -> Result<Self, TryFromSliceError> {
+        bytes.try_into()
+    }
+}
+
+impl FromVector<'_> for String {
+    type Error = Utf8Error;
+
+    fn from_vector(bytes: &[u8]) -> Result<Self, Utf8Error> {
+        std::str::from_utf8(bytes).map(str::to_string)
+    }
+}
@@ -0,0 +1,91 @@
+//! Exchange vectors with cargo-fuzz / libFuzzer corpora
+//!
+//! Fuzz targets benefit from starting with the same vectors that unit tests use, and crashes found
+//! by fuzzing make good regression vectors. libFuzzer corpus and artifact directories are flat
+//! directories of input files, so these functions map between a single argument file in each case
+//! directory and one file per input in a flat directory.
+//!
+//! The same operations are available from the command line with the `cli` cargo feature:
+//!
+//! ```text
+//! cargo test-vectors fuzz-export <corpus dir> <arg> <fuzz corpus dir>
+//! cargo test-vectors fuzz-import <artifact dir> <corpus dir> <arg>
+//! ```
+
+use crate::Corpus;
+use std::path::{Path, PathBuf};
+
+/// Copy the `arg` file from each case in `corpus` into the flat directory `out_dir`
+///
+/// Each file is named after its case, and `out_dir` is created if necessary. Cases without an
+/// `arg` file are skipped. Returns the number of files written.
+pub fn export_seeds(corpus: &Corpus, arg: &str, out_dir: &Path) -> std::io::Result<usize> {
+    std::fs::create_dir_all(out_dir)?;
+
+    let mut count = 0;
+    for case in corpus.cases() {
+        let src = case.path().join(arg);
+        if src.is_file() {
+            std::fs::copy(&src, out_dir.join(case.name()))?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Create a case directory in `corpus_dir` holding each file of `artifact_dir` as its `arg` file
+///
+/// Case directory names are derived from the artifact file names, such as `crash-<hash>`, with
+/// every character other than ASCII alphanumerics replaced by `_` so that test names are valid
+/// identifiers. An artifact whose case directory already holds the same contents is skipped, so
+/// importing is idempotent, while one whose name collides with a different case gets a numeric
+/// suffix, such as `crash_ab_2`. Returns the paths of the new case directories.
+pub fn import_artifacts(
+    artifact_dir: &Path,
+    corpus_dir: &Path,
+    arg: &str,
+) -> std::io::Result<Vec<PathBuf>> {
+    let mut names = vec![];
+    for entres in artifact_dir.read_dir()? {
+        let entry = entres?;
+        if entry.path().metadata()?.is_file() {
+            names.push(entry.file_name());
+        }
+    }
+    names.sort();
+
+    let mut created = vec![];
+    for name in names {
+        let casename: String = name
+            .to_string_lossy()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let contents = std::fs::read(artifact_dir.join(&name))?;
+
+        let mut suffix = 1;
+        let casedir = loop {
+            let casedir = match suffix {
+                1 => corpus_dir.join(&casename),
+                n => corpus_dir.join(format!("{}_{}", casename, n)),
+            };
+            if !casedir.exists() {
+                break Some(casedir);
+            }
+            if std::fs::read(casedir.join(arg)).is_ok_and(|existing| existing == contents) {
+                break None;
+            }
+            suffix += 1;
+        };
+
+        if let Some(casedir) = casedir {
+            std::fs::create_dir_all(&casedir)?;
+            std::fs::write(casedir.join(arg), &contents)?;
+            created.push(casedir);
+        }
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,53 @@
+use crate::Corpus;
+use std::io::Result;
+use std::path::PathBuf;
+use target_test_dir::test_with_dir;
+
+#[test_with_dir]
+fn export_seeds(testdir: PathBuf) -> Result<()> {
+    let corpus = Corpus::open("test-data/example2")?;
+    let out = testdir.join("fuzz-corpus");
+
+    assert_eq!(super::export_seeds(&corpus, "input", &out)?, 1);
+    assert_eq!(
+        std::fs::read(out.join("single_case"))?,
+        b"this is the input\n"
+    );
+
+    assert_eq!(super::export_seeds(&corpus, "missing", &out)?, 0);
+    Ok(())
+}
+
+#[test_with_dir]
+fn import_artifacts(testdir: PathBuf) -> Result<()> {
+    let artifacts = testdir.join("artifacts");
+    std::fs::create_dir(&artifacts)?;
+    std::fs::write(artifacts.join("crash-0a1b"), b"boom")?;
+    std::fs::write(artifacts.join("oom-ff00"), b"big")?;
+
+    let corpus = testdir.join("corpus");
+    let created = super::import_artifacts(&artifacts, &corpus, "input")?;
+    assert_eq!(
+        created,
+        [corpus.join("crash_0a1b"), corpus.join("oom_ff00")]
+    );
+    assert_eq!(std::fs::read(corpus.join("crash_0a1b/input"))?, b"boom");
+
+    // A second import skips existing cases:
+    assert!(super::import_artifacts(&artifacts, &corpus, "input")?.is_empty());
+
+    let names: Vec<String> = Corpus::open(&corpus)?
+        .cases()
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect();
+    assert_eq!(names, ["crash_0a1b", "oom_ff00"]);
+
+    // A different artifact mapping to an existing case name is imported under a new name:
+    std::fs::write(artifacts.join("crash_0a1b"), b"bang")?;
+    let created = super::import_artifacts(&artifacts, &corpus, "input")?;
+    assert_eq!(created, [corpus.join("crash_0a1b_2")]);
+    assert_eq!(std::fs::read(corpus.join("crash_0a1b_2/input"))?, b"bang");
+    assert!(super::import_artifacts(&artifacts, &corpus, "input")?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,352 @@
+//! A libtest-style harness for cases discovered at runtime
+//!
+//! The [macro@crate::test_vectors] macro requires the corpus to exist at compile time. For
+//! corpora which are only available when tests run, such as vectors downloaded in CI, a test
+//! target with `harness = false` can instead call [run] from `main`:
+//!
+//! ```toml
+//! [[test]]
+//! name = "conformance"
+//! harness = false
+//! ```
+//!
+//! ```no_run
+//! // tests/conformance.rs
+//! use test_vectors::Case;
+//!
+//! fn uppercase(case: &Case) -> std::io::Result<()> {
+//!     let input = case.file("input")?;
+//!     let expected = case.file("expected")?;
+//!     assert_eq!(expected, input.to_ascii_uppercase());
+//!     Ok(())
+//! }
+//!
+//! fn main() {
+//!     test_vectors::harness::run("downloaded-vectors", uppercase)
+//! }
+//! ```
+//!
+//! Each case is reported as a test named after the case, and the command line supports a subset
+//! of the libtest interface: name filters, `--exact`, `--skip <filter>`, `--list`, `--ignored`,
+//! `--include-ignored`, and `--quiet`. Options which only affect libtest's output or threading,
+//! such as `--nocapture` or `--test-threads`, are accepted and ignored. Cases run sequentially.
+
+use crate::{Case, Corpus, SortOrder};
+use std::fmt::Debug;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+/// Run `criterion` against every case in `corpus_dir` with arguments from the command line, then
+/// exit the process
+///
+/// This is shorthand for `Harness::new(corpus_dir).run(criterion)`.
+pub fn run<P, F, O>(corpus_dir: P, criterion: F) -> !
+where
+    P: AsRef<Path>,
+    F: Fn(&Case) -> O,
+    O: Outcome,
+{
+    Harness::new(corpus_dir).run(criterion)
+}
+
+/// A configurable runtime test harness over a corpus directory
+pub struct Harness {
+    dir: PathBuf,
+    order: SortOrder,
+    ignore: Box<dyn Fn(&Case) -> bool>,
+}
+
+impl Harness {
+    /// A harness for `corpus_dir`, which is resolved against the current directory
+    pub fn new<P>(corpus_dir: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Harness {
+            dir: corpus_dir.as_ref().to_path_buf(),
+            order: SortOrder::default(),
+            ignore: Box::new(|_| false),
+        }
+    }
+
+    /// Run cases in `order`
+    pub fn sort(mut self, order: SortOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Report cases matching `predicate` as ignored, unless `--ignored` or `--include-ignored`
+    /// are given
+    pub fn ignore_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Case) -> bool + 'static,
+    {
+        self.ignore = Box::new(predicate);
+        self
+    }
+
+    /// Run `criterion` with arguments from the command line, then exit the process
+    ///
+    /// The exit status is 0 if every case passed and 101 otherwise, matching libtest.
+    pub fn run<F, O>(self, criterion: F) -> !
+    where
+        F: Fn(&Case) -> O,
+        O: Outcome,
+    {
+        let args = Args::from_env().unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(1)
+        });
+        let conclusion = self.run_with_args(&args, criterion);
+        std::process::exit(conclusion.exit_code())
+    }
+
+    /// Run `criterion` with explicit `args`, returning a summary rather than exiting
+    ///
+    /// # Panics
+    ///
+    /// If the corpus directory cannot be read.
+    pub fn run_with_args<F, O>(self, args: &Args, criterion: F) -> Conclusion
+    where
+        F: Fn(&Case) -> O,
+        O: Outcome,
+    {
+        let corpus = Corpus::open_sorted(&self.dir, self.order)
+            .unwrap_or_else(|e| panic!("could not open corpus {:?}: {}", self.dir, e));
+
+        let mut conclusion = Conclusion::default();
+        let selected: Vec<&Case> = corpus
+            .cases()
+            .iter()
+            .filter(|case| {
+                // Like libtest, `--ignored` filters out the cases which are not ignored:
+                let keep = args.selects(case.name()) && (!args.ignored || (self.ignore)(case));
+                if !keep {
+                    conclusion.filtered_out += 1;
+                }
+                keep
+            })
+            .collect();
+
+        if args.list {
+            for case in &selected {
+                println!("{}: test", case.name());
+            }
+            println!();
+            println!("{} tests, 0 benchmarks", selected.len());
+            return conclusion;
+        }
+
+        println!();
+        println!("running {} tests", selected.len());
+
+        let mut failures = vec![];
+        for case in selected {
+            let ignored = (self.ignore)(case);
+            if ignored && !args.ignored && !args.include_ignored {
+                report(args, case.name(), "ignored", 'i');
+                conclusion.ignored += 1;
+                continue;
+            }
+
+            let outcome = catch_unwind(AssertUnwindSafe(|| criterion(case).into_result()));
+            match outcome {
+                Ok(Ok(())) => {
+                    report(args, case.name(), "ok", '.');
+                    conclusion.passed += 1;
+                }
+                Ok(Err(message)) => {
+                    report(args, case.name(), "FAILED", 'F');
+                    failures.push((case.name().to_string(), Some(message)));
+                }
+                Err(_) => {
+                    // The panic hook has already printed the panic message:
+                    report(args, case.name(), "FAILED", 'F');
+                    failures.push((case.name().to_string(), None));
+                }
+            }
+        }
+        conclusion.failed = failures.len();
+
+        if args.quiet {
+            println!();
+        }
+        if !failures.is_empty() {
+            println!();
+            println!("failures:");
+            for (name, message) in failures.iter() {
+                if let Some(message) = message {
+                    println!();
+                    println!("---- {} ----", name);
+                    println!("{}", message);
+                }
+            }
+            println!();
+            println!("failures:");
+            for (name, _) in failures.iter() {
+                println!("    {}", name);
+            }
+        }
+
+        println!();
+        println!(
+            "test result: {}. {} passed; {} failed; {} ignored; 0 measured; {} filtered out",
+            if conclusion.has_failed() {
+                "FAILED"
+            } else {
+                "ok"
+            },
+            conclusion.passed,
+            conclusion.failed,
+            conclusion.ignored,
+            conclusion.filtered_out,
+        );
+        println!();
+
+        conclusion
+    }
+}
+
+fn report(args: &Args, name: &str, status: &str, terse: char) {
+    use std::io::Write;
+
+    if args.quiet {
+        print!("{}", terse);
+        std::io::stdout().flush().ok();
+    } else {
+        println!("test {} ... {}", name, status);
+    }
+}
+
+/// The result of a criterion, mirroring the return types supported by `#[test]` functions
+pub trait Outcome {
+    /// Convert to a failure message on error
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl Outcome for () {
+    fn into_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E> Outcome for Result<(), E>
+where
+    E: Debug,
+{
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(|e| format!("Error: {:?}", e))
+    }
+}
+
+/// The supported subset of libtest command line arguments
+#[derive(Debug, Default)]
+pub struct Args {
+    /// List selected cases rather than running them
+    pub list: bool,
+    /// Run only ignored cases
+    pub ignored: bool,
+    /// Run both ignored and non-ignored cases
+    pub include_ignored: bool,
+    /// Filters must match case names exactly rather than as substrings
+    pub exact: bool,
+    /// Print one character per case rather than one line
+    pub quiet: bool,
+    /// Run only cases matching any of these filters, or all cases if empty
+    pub filters: Vec<String>,
+    /// Skip cases matching any of these filters
+    pub skip: Vec<String>,
+}
+
+impl Args {
+    /// Parse the arguments of the current process
+    pub fn from_env() -> Result<Self, String> {
+        Args::parse(std::env::args().skip(1))
+    }
+
+    /// Parse arguments, excluding the program name
+    pub fn parse<I, S>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => (flag.to_string(), Some(value)),
+                _ => (arg.clone(), None),
+            };
+            let mut value = |flag: &str| {
+                inline_value
+                    .map(str::to_string)
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("missing value for {}", flag))
+            };
+
+            match flag.as_str() {
+                "--list" => parsed.list = true,
+                "--ignored" => parsed.ignored = true,
+                "--include-ignored" => parsed.include_ignored = true,
+                "--exact" => parsed.exact = true,
+                "-q" | "--quiet" => parsed.quiet = true,
+                "--skip" => parsed.skip.push(value("--skip")?),
+                "--nocapture" | "--show-output" => {}
+                "--test-threads" | "--color" | "--format" => {
+                    value(&flag)?;
+                }
+                other if other.starts_with('-') => {
+                    return Err(format!("unsupported argument: {}", other));
+                }
+                _ => parsed.filters.push(arg),
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn selects(&self, name: &str) -> bool {
+        let matches = |filter: &String| {
+            if self.exact {
+                name == filter
+            } else {
+                name.contains(filter.as_str())
+            }
+        };
+
+        (self.filters.is_empty() || self.filters.iter().any(matches))
+            && !self.skip.iter().any(matches)
+    }
+}
+
+/// A summary of a harness run
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Conclusion {
+    /// Cases which passed
+    pub passed: usize,
+    /// Cases which failed by returning an error or panicking
+    pub failed: usize,
+    /// Cases which were reported as ignored
+    pub ignored: usize,
+    /// Cases excluded by filters
+    pub filtered_out: usize,
+}
+
+impl Conclusion {
+    /// Whether any case failed
+    pub fn has_failed(&self) -> bool {
+        self.failed > 0
+    }
+
+    /// The process exit code libtest uses for this result
+    pub fn exit_code(&self) -> i32 {
+        if self.has_failed() {
+            101
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,99 @@
+use super::{Args, Conclusion, Harness};
+use std::path::{Path, PathBuf};
+use target_test_dir::test_with_dir;
+
+/// Create a corpus of three empty cases, `example1` to `example3`, in `testdir`
+fn example_corpus(testdir: &Path) -> std::io::Result<PathBuf> {
+    for case in ["example1", "example2", "example3"] {
+        std::fs::create_dir_all(testdir.join(case))?;
+    }
+    Ok(testdir.to_path_buf())
+}
+
+#[test]
+fn parse_args() {
+    let args = Args::parse([
+        "--exact",
+        "--skip",
+        "beta",
+        "--test-threads=4",
+        "--nocapture",
+        "alpha",
+    ])
+    .unwrap();
+
+    assert!(args.exact);
+    assert!(!args.list);
+    assert_eq!(args.skip, ["beta"]);
+    assert_eq!(args.filters, ["alpha"]);
+
+    assert!(Args::parse(["--bogus"]).is_err());
+    assert!(Args::parse(["--skip"]).is_err());
+}
+
+#[test]
+fn args_select_names() {
+    let args = Args::parse(["case", "--skip", "case_2"]).unwrap();
+    assert!(args.selects("case_1"));
+    assert!(!args.selects("case_2"));
+    assert!(!args.selects("other"));
+
+    let args = Args::parse(["--exact", "case"]).unwrap();
+    assert!(args.selects("case"));
+    assert!(!args.selects("case_1"));
+}
+
+#[test_with_dir]
+fn run_counts_outcomes(testdir: PathBuf) -> std::io::Result<()> {
+    let corpus = example_corpus(&testdir)?;
+    let conclusion =
+        Harness::new(corpus).run_with_args(&Args::default(), |case| match case.name() {
+            "example2" => Ok(()),
+            _ => Err("expected failure"),
+        });
+
+    assert_eq!(
+        conclusion,
+        Conclusion {
+            passed: 1,
+            failed: 2,
+            ignored: 0,
+            filtered_out: 0,
+        }
+    );
+    assert_eq!(conclusion.exit_code(), 101);
+    Ok(())
+}
+
+#[test_with_dir]
+fn run_ignored_and_filtered(testdir: PathBuf) -> std::io::Result<()> {
+    let corpus = example_corpus(&testdir)?;
+    let harness = || Harness::new(&corpus).ignore_if(|case| case.name() == "example3");
+    let criterion = |_: &crate::Case| -> Result<(), String> { panic!("criterion should not run") };
+
+    let args = Args::parse(["example3"]).unwrap();
+    let conclusion = harness().run_with_args(&args, criterion);
+    assert_eq!(
+        conclusion,
+        Conclusion {
+            passed: 0,
+            failed: 0,
+            ignored: 1,
+            filtered_out: 2,
+        }
+    );
+
+    let args = Args::parse(["--ignored"]).unwrap();
+    let conclusion = harness().run_with_args(&args, |_| ());
+    assert_eq!(
+        conclusion,
+        Conclusion {
+            passed: 1,
+            failed: 0,
+            ignored: 0,
+            filtered_out: 2,
+        }
+    );
+    assert_eq!(conclusion.exit_code(), 0);
+    Ok(())
+}
@@ -86,6 +86,21 @@
 //! traversing symlinks). Non-directories are ignored, and it's good practice to have a `README.md`
 //! file explaining the corpus.
 //!
+//! Each test is named `<criterion function name>_<case directory name>`, so case directory names
+//! must consist of identifier characters. A case directory name which is not valid UTF-8, as
+//! sometimes found in fuzz-derived corpora, is losslessly escaped: ASCII alphanumerics are kept,
+//! `_` becomes `__`, and every other byte becomes `_xHH`. Since such a path cannot be passed to
+//! `include_bytes!`, the file contents of those cases are embedded directly by the macro instead,
+//! and cargo cannot tell when they change, so touch a source file to rebuild after editing one.
+//! Every other file the macro reads, including metadata, `expect_error` and `extends.toml` markers,
+//! and manifests, is registered with the compiler so that editing it rebuilds the tests.
+//!
+//! A corpus directory which contains no case directories is a compile error, since a typo in
+//! `dir` which points at an existing but empty directory would otherwise silently generate zero
+//! tests. Pass `allow_empty = true` if an empty corpus is intended. Conversely, `min_cases = N`
+//! makes it a compile error for the corpus to contain fewer than `N` cases, which guards against
+//! accidentally truncated vendored corpora.
+//!
 //! Inside a case directory, only the paths derived from the criterion function argument names are
 //! accessed, and other contents are ignored, so a good practice is a `README.md` explaining
 //! the intention of the case. Another nuance of this behavior is that different criterion
@@ -132,28 +147,204 @@
 //! against the same test vector `input` files, while each function reads a different test vector
 //! for its specific functionality, ie `underscores` vs `elided`.
 //!
+//! Because unused files are ignored, a misspelled file such as `expectd` would silently go
+//! unchecked. The `strict = true` macro argument makes it a compile error for a case directory to
+//! contain any entry which is not consumed by an argument of that criterion function. `README.md`
+//! is always allowed, and additional shell-style patterns (where `*` matches any run of characters
+//! and `?` any single character) may be exempted with one or more `ignore` arguments, such as
+//! `strict = true, ignore = "*.txt", ignore = "elided"`. The latter form is useful when several
+//! criterion functions share a corpus, because `strict` only knows about the arguments of the
+//! function it annotates.
+//!
+//! Cases often share a large common input, such as a key file or dictionary. Rather than copying
+//! it into every case directory, an argument file missing from a case directory is read from the
+//! `_shared` directory of the corpus if present there, and otherwise from a file at the top level
+//! of the corpus directory. A case can still override a shared file with its own copy. The
+//! `_shared` directory is never a case, so an existing case of that name must be renamed to keep
+//! being tested, and a top level directory is a case rather than a shared argument. The runtime
+//! [Case::file] applies the same fallback.
+//!
+//! Similarly, a case which differs from another by only a file or two can inherit the rest from
+//! that base case with an `extends.toml` file, such as:
+//!
+//! ```toml
+//! extends = "../base-case"
+//! ```
+//!
+//! The path is relative to the case directory. Argument files missing from the case are read from
+//! its base case, which may in turn extend another, before falling back to shared files. A
+//! missing base case, a malformed `extends.toml`, or a cycle of cases extending each other is a
+//! compile error, and `extends.toml` is never reported as unused by `strict`.
+//!
+//! # Case Ordering
+//!
+//! Cases are always generated sorted by case directory name, independent of the order in which
+//! the filesystem lists them, so the macro expansion is identical across machines. By default names
+//! are compared by byte order, so `case_10` precedes `case_2`. The `sort = "natural"` macro
+//! argument compares runs of ASCII digits numerically instead, so `case_2` precedes `case_10`.
+//!
+//! # Testing Multiple Implementations
+//!
+//! To validate the same vectors against several implementations of a trait, a criterion function
+//! may take a single type parameter and the `types` macro argument names each implementation. The
+//! macro generates a test for every combination of case and implementation, named
+//! `<criterion function name>_<case directory name>_<implementation name>`:
+//!
+//! ```
+//! use test_vectors::{test_vectors, Utf8Str};
+//!
+//! trait Replacer {
+//!     fn replace(input: &str) -> String;
+//! }
+//!
+//! struct StrReplace;
+//!
+//! impl Replacer for StrReplace {
+//!     fn replace(input: &str) -> String {
+//!         input.replace(' ', "_")
+//!     }
+//! }
+//!
+//! struct CharMap;
+//!
+//! impl Replacer for CharMap {
+//!     fn replace(input: &str) -> String {
+//!         input.chars().map(|c| if c == ' ' { '_' } else { c }).collect()
+//!     }
+//! }
+//!
+//! #[test_vectors(
+//! # doctest = true,
+//!   dir = "test-data/example2",
+//!   types(Std = StrReplace, Chars = CharMap),
+//! )]
+//! fn replace<R: Replacer>(input: Utf8Str<'static>, underscores: Utf8Str<'static>) {
+//!     assert_eq!(underscores, R::replace(&input));
+//! }
+//! ```
+//!
+//! This generates `replace_single_case_Std` and `replace_single_case_Chars`. The argument types and
+//! return type of the criterion function are copied into each test, so they may not refer to the
+//! type parameter.
+//!
+//! # Parameterizing Cases With Literal Values
+//!
+//! Each case can also be run under several configurations with the `params` macro argument. Each
+//! criterion function argument named in `params` takes its value from the given list of rust
+//! expressions rather than from a case file, and a test is generated for every combination of case
+//! and values, named `<criterion function name>_<case directory name>_<argument>_<value>`:
+//!
+//! ```
+//! use test_vectors::{test_vectors, Utf8Str};
+//!
+//! #[test_vectors(
+//! # doctest = true,
+//!   dir = "test-data/example2",
+//!   params(repeat = [1, 3], sep = ["and", "or"]),
+//! )]
+//! fn repeat(input: Utf8Str<'static>, underscores: Utf8Str<'static>, repeat: usize, sep: &str) {
+//!     let output = vec![input.replace(' ', "_"); repeat].join(sep);
+//!     assert_eq!(output, vec![&*underscores; repeat].join(sep));
+//! }
+//! ```
+//!
+//! This generates four tests for the single case, from `repeat_single_case_repeat_1_sep_and`
+//! through `repeat_single_case_repeat_3_sep_or`. Parameter values are passed as-is, without any
+//! `FromVector` conversion. When combined with `types`, the implementation name precedes the
+//! parameter values in test names.
+//!
+//! # Benchmarks
+//!
+//! With the `bench` cargo feature enabled, the `bench_vectors` macro generates a
+//! [criterion](https://docs.rs/criterion) benchmark group from the same corpus, so benchmarks
+//! measure exactly the vectors that tests check. It accepts the same macro arguments and case
+//! discovery rules as [macro@test_vectors], except `doctest`. The annotated function is replaced
+//! by a function of the same name taking `&mut criterion::Criterion`, which benchmarks each case
+//! under its case name:
+//!
+//! ```text
+//! use test_vectors::bench_vectors;
+//!
+//! #[bench_vectors(dir = "test-data/example2")]
+//! fn replace(input: &[u8], underscores: &[u8]) {
+//!     assert_eq!(underscores, my_crate::replace_spaces(input));
+//! }
+//!
+//! criterion::criterion_group!(benches, replace);
+//! criterion::criterion_main!(benches);
+//! ```
+//!
+//! The calling crate must depend on `criterion` itself, typically in `[dev-dependencies]` with a
+//! `[[bench]]` target using `harness = false`. Argument conversion happens in the benchmark setup,
+//! so only the criterion function body is measured.
+//!
+//! # Runtime Corpus Access
+//!
+//! Code which cannot use compile-time generation, such as build scripts, fuzz harnesses, or
+//! custom test harnesses, can enumerate a corpus at runtime with [Corpus]. It applies the same case
+//! discovery, naming, and ordering rules as the macros.
+//!
+//! For corpora which only exist when tests run, such as vectors downloaded in CI, the [harness]
+//! module provides a libtest-style runner for `harness = false` test targets.
+//!
+//! The [fuzz] module exports case files as seeds for a cargo-fuzz corpus and imports fuzzing
+//! crash artifacts back as new cases.
+//!
+//! # Command Line Tool
+//!
+//! With the `cli` feature, this crate provides a `cargo test-vectors` subcommand. To add a case,
+//! run it from within the crate:
+//!
+//! ```text
+//! cargo test-vectors new <corpus dir> <case>
+//! ```
+//!
+//! This scans the crate's sources for criterion functions using the corpus, then creates the case
+//! directory with an empty file for every argument those criteria read. Existing files, and
+//! arguments provided by shared files, are left as-is.
+//!
+//! To find corpus mistakes in CI without compiling the test suite, run:
+//!
+//! ```text
+//! cargo test-vectors check [<corpus dir>...]
+//! ```
+//!
+//! This reports case directories missing argument files, containing entries no argument reads,
+//! with names which do not produce valid test names, or with invalid metadata or `extends.toml`
+//! files, along with corpora which have too few cases. Any corpus directory given on the command
+//! line which no criterion function uses is also reported. The command fails if there are any
+//! problems.
+//!
 //! # Automatic Input Conversion From Bytes
 //!
-//! Arguments to criterion test functions are translated with `TryFrom<&[u8]>` against the
-//! file contents, which are available as `&[u8]`. Since this trait provides a blanket
-//! implementation, an argument of type `&[u8]` is the basic supported type.
+//! Arguments to criterion test functions are translated with the [FromVector] trait against the
+//! file contents, which are available as `&[u8]`. An argument of type `&[u8]` is the basic
+//! supported type. Owned types such as `Vec<u8>` convert just as well, and a `String` argument is
+//! decoded as UTF-8.
 //!
-//! For other types, this can take care of some boiler-plate for converting inputs by using a
-//! standard rust trait. This approach, versus supporting customizeable conversions in the macro
-//! interface keeps the macro interface and logic simpler by relying on this standard rust trait.
+//! For other types, this can take care of some boiler-plate for converting inputs by implementing
+//! a single trait. This approach, versus supporting customizeable conversions in the macro
+//! interface keeps the macro interface and logic simpler by relying on this trait.
 //!
 //! The result of conversion is unwrapped, so any failure of conversion causes a panic and the test
 //! case will fail. The call site looks something like:
 //!
 //! ```text
-//! <T>::try_from(include_bytes!(…)).unwrap()
+//! <T as FromVector>::from_vector(include_bytes!(…)).unwrap()
 //! ```
 //!
+//! [FromVector] ties the lifetime of a borrowing type such as [Utf8Str] to the bytes it converts
+//! from. Embedded bytes are `'static`, but a criterion function written with an elided lifetime,
+//! such as `Utf8Str<'_>`, can also be called with vectors loaded at runtime, such as with
+//! [Case::load] or [Case::file].
+//!
 //! Recall in the first example, we explicitly called [std::str::from_utf8] to convert the byte
 //! slice parameters. This is an example of a conversion function that is not available via
-//! `TryFrom<&[u8]>` (because there might be multiple ways to convert bytes into a `str`). So that
-//! example highlights how test criterion functions may need to rely on newtype wrapper types to
-//! perform conversions. The [test-vectors](crate) crate provides some commonly needed wrapper types, such as [Utf8Str] for that case. Compare the example in the [Utf8Str] docs to the first example above.
+//! [FromVector] for `&str` (because there might be multiple ways to convert bytes into a `str`).
+//! So that example highlights how test criterion functions may need to rely on newtype wrapper
+//! types to perform conversions. The [test-vectors](crate) crate provides some commonly needed wrapper types, such as [Utf8Str] for that case, or its owned counterpart [Utf8String]. Compare the example in the [Utf8Str] docs to the first example above.
+//! Likewise [Lines] splits a UTF-8 file into lines, optionally skipping blank and comment lines,
+//! and, with the `csv` feature, `Csv<T>` deserializes the records of a CSV file with a header row.
 //!
 //! If a test needs some custom conversion, it may need to implement a custom new-type wrapper, as
 //! the next example shows:
@@ -168,7 +359,7 @@
 //!
 //! ```
 //! use serde::Deserialize;
-//! use test_vectors::test_vectors;
+//! use test_vectors::{test_vectors, FromVector};
 //!
 //! #[derive(Deserialize)]
 //! struct AppType {
@@ -191,11 +382,11 @@
 //!     }
 //! }
 //!
-//! impl TryFrom<&[u8]> for AppTypeFromJson
+//! impl FromVector<'_> for AppTypeFromJson
 //! {
 //!     type Error = serde_json::Error;
 //!
-//!     fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
+//!     fn from_vector(input: &[u8]) -> Result<Self, Self::Error> {
 //!         serde_json::from_slice(input).map(AppTypeFromJson)
 //!     }
 //! }
@@ -214,8 +405,261 @@
 //!
 //! The return type of a criterion function is replicated directly for each test case, and the test
 //! returns the criterion function result unaltered. Criterion functions can return `()` or [Result] with identical behavior to unit tests.
+//!
+//! # Expected Failures
+//!
+//! Negative vectors, such as malformed inputs, pass only when they are rejected. A case directory
+//! containing an `expect_error` file generates a test which fails unless an argument conversion
+//! fails or the criterion function returns `Err`. If the file is not empty, the error's
+//! [Display](std::fmt::Display) text must also contain its contents, with surrounding whitespace
+//! trimmed. For example, `test-data/numbers/bad_digit/expect_error` might contain
+//! `invalid digit`.
+//!
+//! A corpus of only invalid cases, such as an `invalid/` directory next to the valid corpus, can
+//! instead use `expect_error = true` so that every case is expected to fail:
+//!
+//! ```
+//! use std::num::ParseIntError;
+//! use test_vectors::{test_vectors, Utf8Str};
+//!
+//! #[test_vectors(
+//! # doctest = true,
+//!     dir = "test-data/invalid",
+//!     expect_error = true,
+//! )]
+//! fn parse_rejects(input: Utf8Str<'static>) -> Result<u32, ParseIntError> {
+//!     input.parse()
+//! }
+//! ```
+//!
+//! For these cases, conversion and criterion errors must implement `Display`. A panic is still a
+//! test failure, and a criterion function returning `()` can only fail through conversion. The
+//! `expect_error` file is never reported as unused by `strict`, and these cases are not
+//! benchmarked.
+//!
+//! # Saving Actual Outputs
+//!
+//! When a comparison with [assert_vector_eq] fails, the actual value can be saved for inspection
+//! with external tools, such as a diff viewer or a format-specific tool, which is a key benefit of
+//! keeping vectors in external files. If the `TEST_VECTORS_ACTUAL_DIR` environment variable is
+//! set when tests run, the corpus tree is mirrored beneath that directory, ie a mismatch of
+//! `expected` in case `alpha` of `test-data/example1` is written to
+//! `$TEST_VECTORS_ACTUAL_DIR/test-data/example1/alpha/expected.actual`. This suits CI, where the
+//! directory can be kept as a build artifact.
+//!
+//! Otherwise, the `write_actual = true` macro argument writes the value beside the case file, ie
+//! `test-data/example1/alpha/expected.actual`, which is convenient locally. Files ending in
+//! `.actual` are never reported as unused by `strict`, though they are usually best listed in
+//! `.gitignore`.
+//!
+//! Generated tests register their case for the duration of the call, which [CaseInfo::current]
+//! exposes to any other helpers.
+//!
+//! # Case Metadata
+//!
+//! A case directory may contain a `meta.toml` or `meta.json` file describing the case, with any of
+//! these fields:
+//!
+//! ```toml
+//! description = "A message with an empty payload"
+//! tags = ["fast", "edge-case"]
+//! references = ["RFC 9999 section 4.2"]
+//! ```
+//!
+//! The macro reads metadata when it generates tests, and an invalid or misspelled field is a
+//! compile error. When a test panics or its criterion function returns `Err`, the description and
+//! references of its case are printed with the failure. The `tags` macro argument generates only
+//! the cases selected by tag, where a leading `!` excludes a tag: `tags = ["fast"]` generates the
+//! cases tagged `fast`, and `tags = ["!slow"]` generates every case not tagged `slow`. Within a
+//! criterion function, [CaseInfo::meta] gives the metadata of the running case:
+//!
+//! ```
+//! use test_vectors::{test_vectors, CaseInfo};
+//!
+//! #[test_vectors(
+//! # doctest = true,
+//!     dir = "test-data/conformance",
+//!     tags = ["!slow"],
+//! )]
+//! fn decode(input: &[u8]) {
+//!     let case = CaseInfo::current().unwrap();
+//!     assert!(!case.meta().has_tag("slow"));
+//!     if case.meta().has_tag("edge-case") {
+//!         assert!(input.is_empty());
+//!     }
+//! }
+//! ```
+//!
+//! Metadata files are never reported as unused by `strict`.
+//!
+//! Cases can also be selected by tag when tests run, without recompiling, with the
+//! `TEST_VECTORS_TAGS` environment variable. It takes a comma separated list in the same form as
+//! the `tags` argument, ie `TEST_VECTORS_TAGS=fast,!network cargo test`. The standard test
+//! harness cannot skip a test once it runs, so a deselected case is reported as passing without
+//! calling the criterion function, and reports the skip on stderr regardless of output capture.
+//! For this reason, unless every case is expected to fail, a criterion function must return `()`
+//! or a `Result` with success type `()`.
+//!
+//! # Verifying Corpus Integrity
+//!
+//! Vendored conformance vectors should match their upstream release exactly. With
+//! `manifest = "SHA256SUMS"`, the macro reads a manifest in the `sha256sum` format from the corpus
+//! directory, where each line gives a SHA-256 digest and a path relative to the corpus directory,
+//! such as `alpha/input`. It is a compile error for a case file read by an argument to be missing
+//! from the manifest or to have a different digest, or for a listed file not to exist.
+//!
+//! An upstream checksum file can often be used directly. Otherwise, the [command line
+//! tool](#command-line-tool) regenerates a manifest of every file in the corpus case directories:
+//!
+//! ```text
+//! cargo test-vectors manifest <corpus dir> [<manifest name>]
+//! ```
+//!
+//! # Testing Command Line Programs
+//!
+//! A binary target of the crate can be tested by naming it with `bin`, from an integration test
+//! where cargo provides its path in `CARGO_BIN_EXE_<name>`. It is a compile error if cargo does not
+//! provide the path, such as for a misspelled name or in unit tests:
+//!
+//! ```ignore
+//! use test_vectors::test_vectors;
+//!
+//! #[test_vectors(dir = "tests/cli", bin = "mytool")]
+//! fn mytool() {}
+//! ```
+//!
+//! Each case runs the binary with the command line arguments in its `args` file, the environment
+//! variables in its `env` file, and the standard input in its `stdin` file, then compares standard output and standard error against its
+//! `stdout` and `stderr` files, and the exit code against its `status` file. Every file is
+//! optional: without `status` the binary must exit with `0`, and outputs without an expected
+//! file are not checked. These files may come from shared files or a base case like any other, and
+//! are never reported as unused by `strict`. The criterion function runs after the binary passes,
+//! with any arguments read from case files as usual, for further checks.
+//!
+//! The [cmd::Cmd] type runs any program with the contents of these files, such as for a
+//! [Corpus] opened at runtime. The file formats are described in the [cmd] module.
+//!
+//! # Environment Variables and Working Directories
+//!
+//! An argument of type [Env] parses a case file of `KEY=VALUE` lines, conventionally named `env`.
+//! Tests run concurrently within one process, so rather than setting the variables for the whole
+//! process, pass them to a child process with [Env::apply].
+//!
+//! A criterion which writes files, or runs a program expecting its inputs in the current
+//! directory, can take an argument of type [CaseTempDir]. This receives a fresh copy of the case
+//! directory in a temporary directory, whatever the argument is named, along with the files it
+//! inherits from base cases and shared files it falls back to. The copy is removed when the
+//! criterion succeeds, and kept with its path printed when it panics or returns `Err`, for
+//! debugging. Because the criterion may read any entry of the copy, `strict` does not apply to it.
+//!
+//! # Directory-Valued Vectors
+//!
+//! An argument of type [Tree] names a subdirectory of the case rather than a file, such as an
+//! `expected_tree/` directory holding the expected output of unpacking an archive, and every file
+//! beneath it is embedded as a snapshot. Like [CaseTempDir], the type is recognized by name, so it
+//! must be written as `Tree` or a path ending in `Tree`. It is a compile error for a `Tree`
+//! argument not to name a directory, or for an argument of another type to name one. Trees
+//! produced by the code under test can be snapshotted with [Tree::read] and compared with
+//! [assert_tree_eq], which reports added, removed, and changed paths along with a diff of each
+//! changed file. A mismatched actual tree is saved as a `<name>.actual` directory like other
+//! [actual outputs](#saving-actual-outputs).
 
+mod caseinfo;
+pub mod cmd;
+mod corpus;
+#[cfg(feature = "csv")]
+mod csvrows;
+pub mod diff;
+mod envfile;
+mod fromvector;
+pub mod fuzz;
+pub mod harness;
+mod lines;
+mod tempdir;
+mod tree;
 mod utf8str;
 
-pub use self::utf8str::Utf8Str;
+#[doc(hidden)]
+pub use self::caseinfo::CaseGuard;
+pub use self::caseinfo::{CaseInfo, CaseMeta, ACTUAL_DIR_VAR, TAGS_VAR};
+pub use self::corpus::{Case, Corpus};
+#[cfg(feature = "csv")]
+pub use self::csvrows::Csv;
+pub use self::envfile::Env;
+pub use self::fromvector::FromVector;
+pub use self::lines::{Lines, LinesIter};
+pub use self::tempdir::CaseTempDir;
+pub use self::tree::Tree;
+pub use self::utf8str::{Utf8Str, Utf8String};
+pub use test_vectors_core::listdir::SortOrder;
 pub use test_vectors_macro::test_vectors;
+
+#[cfg(feature = "bench")]
+pub use test_vectors_macro::bench_vectors;
+
+/// Assert that two byte or string values are equal, with a readable diff on failure
+///
+/// Both values may be any `AsRef<[u8]>` type, such as `&[u8]`, `String`, or [Utf8Str]. If they
+/// differ, the panic message names the expected value and shows a [diff::diff]: a unified line
+/// diff if both are UTF-8, or a hexdump with offsets otherwise. By default, the expected value is
+/// named after its expression, which for a criterion function argument is the case file name. An
+/// explicit name may be given as a third argument.
+///
+/// Within a generated test, a mismatched actual value may also be saved to a file named after the
+/// expected value; see [Saving Actual Outputs](crate#saving-actual-outputs).
+///
+/// ```
+/// use test_vectors::{assert_vector_eq, test_vectors, Utf8Str};
+///
+/// #[test_vectors(
+/// # doctest = true,
+///   dir = "test-data/example1"
+/// )]
+/// fn replace(input: Utf8Str<'static>, expected: Utf8Str<'static>) {
+///     assert_vector_eq!(expected, input.replace(' ', "_"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_vector_eq {
+    ( $expected:expr, $actual:expr $(,)? ) => {
+        $crate::diff::assert_vector_eq(stringify!($expected), &$expected, &$actual)
+    };
+    ( $expected:expr, $actual:expr, $name:expr $(,)? ) => {
+        $crate::diff::assert_vector_eq($name, &$expected, &$actual)
+    };
+}
+
+/// Assert that two [Tree] snapshots are equal, with a report of differing paths on failure
+///
+/// The panic message lists each added, removed, and changed path, with a [diff::diff] of each
+/// changed file, as described for [diff::tree_diff]. As with [assert_vector_eq], the expected tree
+/// is named after its expression unless a name is given as a third argument, and a mismatched
+/// actual tree is saved as a `<name>.actual` directory in the same way.
+///
+/// ```
+/// use test_vectors::{assert_tree_eq, test_vectors, CaseTempDir, Tree, Utf8Str};
+///
+/// #[test_vectors(
+/// # doctest = true,
+///   dir = "test-data/trees"
+/// )]
+/// fn unpack(input: Utf8Str<'static>, expected_tree: Tree, workdir: CaseTempDir) {
+///     let outdir = workdir.join("unpacked");
+///     for line in input.lines() {
+///         let (path, contents) = line.split_once('=').unwrap();
+///         let path = outdir.join(path);
+///         std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+///         std::fs::write(path, contents).unwrap();
+///     }
+///     assert_tree_eq!(expected_tree, Tree::read(outdir).unwrap());
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_tree_eq {
+    ( $expected:expr, $actual:expr $(,)? ) => {
+        $crate::diff::assert_tree_eq(stringify!($expected), &$expected, &$actual)
+    };
+    ( $expected:expr, $actual:expr, $name:expr $(,)? ) => {
+        $crate::diff::assert_tree_eq($name, &$expected, &$actual)
+    };
+}
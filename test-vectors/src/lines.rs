@@ -0,0 +1,123 @@
+use crate::FromVector;
+use std::str::Utf8Error;
+
+/// Convenience type for tests with newline-separated lists
+///
+/// A `Lines<'a>` decodes a case file as UTF-8 with [FromVector] or `TryFrom<&[u8]>`, and iterates
+/// over its lines like [str::lines], so either `\n` or `\r\n` ends a line and a final line ending
+/// is optional. By default every line is produced, and [Lines::skip_blank] and
+/// [Lines::skip_comments] drop blank or comment lines, which suits annotated lists of inputs.
+///
+/// # Example
+///
+/// ```
+/// use test_vectors::{test_vectors, Lines};
+///
+/// #[test_vectors(
+/// # doctest = true,
+///     dir = "test-data/example2",
+/// )]
+/// fn elide_spaces(input: Lines<'static>, elided: Lines<'static>) {
+///     let output: Vec<String> = input.iter().map(|line| line.replace(' ', "")).collect();
+///     assert_eq!(output, elided.iter().collect::<Vec<_>>());
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Lines<'a> {
+    text: &'a str,
+    skip_blank: bool,
+    comment: Option<&'a str>,
+}
+
+impl<'a> Lines<'a> {
+    /// Skip lines which are empty or only whitespace
+    pub fn skip_blank(mut self) -> Self {
+        self.skip_blank = true;
+        self
+    }
+
+    /// Skip blank lines, and lines starting with `prefix` after any leading whitespace, ie `#`
+    pub fn skip_comments(mut self, prefix: &'a str) -> Self {
+        self.skip_blank = true;
+        self.comment = Some(prefix);
+        self
+    }
+
+    /// Iterate over the lines which are not skipped
+    pub fn iter(&self) -> LinesIter<'a> {
+        LinesIter {
+            inner: self.text.lines(),
+            skip_blank: self.skip_blank,
+            comment: self.comment,
+        }
+    }
+
+    /// The whole decoded text, including any skipped lines
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Lines<'a> {
+    type Error = Utf8Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Lines<'a>, Utf8Error> {
+        std::str::from_utf8(bytes).map(|text| Lines {
+            text,
+            skip_blank: false,
+            comment: None,
+        })
+    }
+}
+
+impl<'a> FromVector<'a> for Lines<'a> {
+    type Error = Utf8Error;
+
+    fn from_vector(bytes: &'a [u8]) -> Result<Lines<'a>, Utf8Error> {
+        Lines::try_from(bytes)
+    }
+}
+
+impl<'a> IntoIterator for Lines<'a> {
+    type Item = &'a str;
+    type IntoIter = LinesIter<'a>;
+
+    fn into_iter(self) -> LinesIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Lines<'a> {
+    type Item = &'a str;
+    type IntoIter = LinesIter<'a>;
+
+    fn into_iter(self) -> LinesIter<'a> {
+        self.iter()
+    }
+}
+
+/// The iterator over the lines of [Lines]
+#[derive(Clone, Debug)]
+pub struct LinesIter<'a> {
+    inner: std::str::Lines<'a>,
+    skip_blank: bool,
+    comment: Option<&'a str>,
+}
+
+impl<'a> Iterator for LinesIter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.inner.find(|line| {
+            let trimmed = line.trim_start();
+            let blank = self.skip_blank && trimmed.is_empty();
+            let comment = self
+                .comment
+                .is_some_and(|prefix| trimmed.starts_with(prefix));
+            !blank && !comment
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+use super::Lines;
+
+const TEXT: &[u8] = b"first\r\n\n  # a comment\n  indented\n   \nlast";
+
+#[test]
+fn every_line_by_default() {
+    let lines = Lines::try_from(TEXT).unwrap();
+    assert_eq!(
+        lines.iter().collect::<Vec<_>>(),
+        ["first", "", "  # a comment", "  indented", "   ", "last"]
+    );
+    assert_eq!(lines.as_str().len(), TEXT.len());
+}
+
+#[test]
+fn skip_blank_and_comments() {
+    let lines = Lines::try_from(TEXT).unwrap();
+    assert_eq!(
+        lines.skip_blank().into_iter().collect::<Vec<_>>(),
+        ["first", "  # a comment", "  indented", "last"]
+    );
+    assert_eq!(
+        lines.skip_comments("#").into_iter().collect::<Vec<_>>(),
+        ["first", "  indented", "last"]
+    );
+}
+
+#[test]
+fn invalid_utf8() {
+    assert!(Lines::try_from(&b"\xff\n"[..]).is_err());
+}
@@ -0,0 +1,143 @@
+use crate::caseinfo::defer_removal;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use test_vectors_core::listdir::{case_chain, SHARED_DIR};
+
+/// A fresh temporary copy of a case directory, for criteria which modify files or need a working
+/// directory
+///
+/// A criterion function argument of this type takes a copy of the case directory rather than the
+/// contents of a case file, so its name does not matter. Every file an argument could fall back
+/// to is copied too: those inherited from base cases with `extends.toml`, then those of the
+/// corpus `_shared` directory, then the files at the top level of the corpus, with the same
+/// precedence as for arguments.
+///
+/// Within a test generated by [test_vectors](crate::test_vectors), the copy is removed once the
+/// criterion function succeeds. If it panics or returns `Err`, the copy is kept and its path
+/// printed for debugging. Outside of a generated test, the copy is removed when dropped unless the
+/// thread is panicking. Use [CaseTempDir::keep] to keep it regardless.
+///
+/// # Example
+///
+/// ```
+/// use test_vectors::{test_vectors, CaseTempDir};
+///
+/// #[test_vectors(
+/// # doctest = true,
+///     dir = "test-data/example2",
+/// )]
+/// fn rewrite_in_place(workdir: CaseTempDir) {
+///     std::fs::write(workdir.join("input"), "rewritten").unwrap();
+///     assert!(workdir.join("underscores").is_file());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CaseTempDir {
+    path: Option<PathBuf>,
+}
+
+impl CaseTempDir {
+    /// Copy `casedir`, any base cases it extends, and the shared files of its corpus, which is its
+    /// parent directory, into a new directory beneath [std::env::temp_dir]
+    pub fn new<P>(casedir: P) -> std::io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let casedir = casedir.as_ref();
+        let located = |e: std::io::Error| {
+            std::io::Error::new(e.kind(), format!("{}: {}", casedir.display(), e))
+        };
+
+        let casename = casedir.file_name().unwrap_or_default().to_string_lossy();
+        let path = loop {
+            let path = std::env::temp_dir().join(format!(
+                "test-vectors-{}-{}-{}",
+                casename,
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed),
+            ));
+            match std::fs::create_dir(&path) {
+                Ok(()) => break path,
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        };
+        // Own the directory before copying so a failed copy is cleaned up:
+        let tempdir = CaseTempDir { path: Some(path) };
+
+        // Copy the lowest precedence files first, so later copies replace them:
+        let corpus = casedir.parent().unwrap_or(casedir);
+        for entres in corpus.read_dir().map_err(located)? {
+            let entry = entres.map_err(located)?;
+            if entry.path().metadata().map_err(located)?.is_file() {
+                std::fs::copy(entry.path(), tempdir.join(entry.file_name())).map_err(located)?;
+            }
+        }
+        let shared = corpus.join(SHARED_DIR);
+        if shared.is_dir() {
+            copy_dir(&shared, tempdir.path()).map_err(located)?;
+        }
+        for dir in case_chain(casedir).map_err(located)?.iter().rev() {
+            copy_dir(dir, tempdir.path()).map_err(located)?;
+        }
+        Ok(tempdir)
+    }
+
+    /// The temporary directory
+    pub fn path(&self) -> &Path {
+        self.path.as_deref().unwrap()
+    }
+
+    /// Keep the temporary directory rather than removing it, returning its path
+    pub fn keep(mut self) -> PathBuf {
+        self.path.take().unwrap()
+    }
+}
+
+impl Deref for CaseTempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        self.path()
+    }
+}
+
+impl AsRef<Path> for CaseTempDir {
+    fn as_ref(&self) -> &Path {
+        self.path()
+    }
+}
+
+impl Drop for CaseTempDir {
+    fn drop(&mut self) {
+        // Within a running case, the copy is kept until the criterion's result is known:
+        if let Some(Err(path)) = self.path.take().map(defer_removal) {
+            if std::thread::panicking() {
+                eprintln!("case temporary directory kept at {}", path.display());
+            } else {
+                std::fs::remove_dir_all(&path).ok();
+            }
+        }
+    }
+}
+
+/// Recursively copy the contents of `src` into the existing directory `dst`, following symlinks
+fn copy_dir(src: &Path, dst: &Path) -> std::io::Result<()> {
+    for entres in src.read_dir()? {
+        let entry = entres?;
+        let target = dst.join(entry.file_name());
+        if entry.path().metadata()?.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
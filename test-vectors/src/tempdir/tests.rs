@@ -0,0 +1,77 @@
+use super::CaseTempDir;
+use crate::{CaseInfo, CaseMeta};
+use std::io::Result;
+use std::path::PathBuf;
+use target_test_dir::test_with_dir;
+
+#[test_with_dir]
+fn copies_case_and_base_cases(testdir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(testdir.join("_shared"))?;
+    std::fs::create_dir_all(testdir.join("base/nested"))?;
+    std::fs::create_dir_all(testdir.join("case"))?;
+    std::fs::write(testdir.join("dictionary"), "root dictionary")?;
+    std::fs::write(testdir.join("input"), "root input")?;
+    std::fs::write(testdir.join("_shared/key"), "shared key")?;
+    std::fs::write(testdir.join("base/input"), "base input")?;
+    std::fs::write(testdir.join("base/nested/data"), "nested")?;
+    std::fs::write(testdir.join("case/extends.toml"), "extends = \"../base\"")?;
+    std::fs::write(testdir.join("case/input"), "case input")?;
+
+    let tempdir = CaseTempDir::new(testdir.join("case"))?;
+    let path = tempdir.to_path_buf();
+    assert_eq!(std::fs::read_to_string(path.join("input"))?, "case input");
+    assert_eq!(std::fs::read_to_string(path.join("nested/data"))?, "nested");
+    assert_eq!(std::fs::read_to_string(path.join("key"))?, "shared key");
+    assert_eq!(
+        std::fs::read_to_string(path.join("dictionary"))?,
+        "root dictionary"
+    );
+    assert!(!path.join("base").exists());
+
+    std::fs::write(path.join("output"), "written by the criterion")?;
+    assert!(!testdir.join("case/output").exists());
+
+    drop(tempdir);
+    assert!(!path.exists());
+    Ok(())
+}
+
+#[test_with_dir]
+fn keep(testdir: PathBuf) -> Result<()> {
+    let path = CaseTempDir::new(&testdir)?.keep();
+    assert!(path.is_dir());
+    std::fs::remove_dir_all(path)
+}
+
+#[test_with_dir]
+fn kept_on_panic(testdir: PathBuf) -> Result<()> {
+    let tempdir = CaseTempDir::new(&testdir)?;
+    let path = tempdir.to_path_buf();
+    let result = std::panic::catch_unwind(move || {
+        let _tempdir = tempdir;
+        panic!("criterion failed");
+    });
+
+    assert!(result.is_err());
+    assert!(path.is_dir());
+    std::fs::remove_dir_all(path)
+}
+
+#[test_with_dir]
+fn removed_after_case_outcome(testdir: PathBuf) -> Result<()> {
+    let in_case = |fail: bool| -> Result<PathBuf> {
+        let case = CaseInfo::new("case", "corpus", None, false, CaseMeta::default()).enter();
+        let path = CaseTempDir::new(&testdir)?.to_path_buf();
+        // Dropped by the criterion, but still present until the case concludes:
+        assert!(path.is_dir());
+        if fail {
+            case.fail();
+        }
+        Ok(path)
+    };
+
+    assert!(!in_case(false)?.exists());
+    let kept = in_case(true)?;
+    assert!(kept.is_dir());
+    std::fs::remove_dir_all(kept)
+}
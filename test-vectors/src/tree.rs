@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use test_vectors_core::listdir::list_tree;
+
+/// An in-memory snapshot of the files in a directory tree, for directory-valued vectors
+///
+/// A criterion function argument of this type names a subdirectory of the case, such as
+/// `expected_tree/`, rather than a file, and the files beneath it are embedded. Outputs
+/// written to disk can be snapshotted with [Tree::read] and compared with
+/// [assert_tree_eq](crate::assert_tree_eq).
+///
+/// Files are keyed by their path relative to the tree root with `/` separators, in byte order.
+/// Directories are implied by the files within them, so empty directories are not represented,
+/// just as they cannot be committed to git.
+///
+/// # Example
+///
+/// ```
+/// use test_vectors::{assert_tree_eq, Tree};
+///
+/// let mut expected = Tree::new();
+/// expected.insert("elided", "thisistheinput\n");
+/// expected.insert("input", "this is the input\n");
+/// expected.insert("underscores", "this_is_the_input\n");
+///
+/// let actual = Tree::read("test-data/example2/single_case")?;
+/// assert_tree_eq!(expected, actual);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tree {
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl Tree {
+    /// An empty tree
+    pub fn new() -> Self {
+        Tree::default()
+    }
+
+    /// Snapshot every file beneath the directory `dir`, following symlinks
+    ///
+    /// It is an [InvalidData](std::io::ErrorKind::InvalidData) error for a name beneath `dir` not
+    /// to be valid UTF-8, since tree paths are strings.
+    pub fn read<P>(dir: P) -> std::io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        let mut tree = Tree::new();
+        for relpath in list_tree(dir)? {
+            let contents = std::fs::read(dir.join(&relpath))?;
+            tree.files.insert(relpath, contents);
+        }
+        Ok(tree)
+    }
+
+    /// Build a tree from embedded files; used by generated code
+    #[doc(hidden)]
+    pub fn from_entries(entries: &[(&str, &[u8])]) -> Self {
+        let mut tree = Tree::new();
+        for (path, contents) in entries {
+            tree.insert(*path, *contents);
+        }
+        tree
+    }
+
+    /// Add or replace the file at the relative `path`, such as `src/lib.rs`
+    pub fn insert<P, C>(&mut self, path: P, contents: C)
+    where
+        P: Into<String>,
+        C: AsRef<[u8]>,
+    {
+        self.files.insert(path.into(), contents.as_ref().to_vec());
+    }
+
+    /// Remove the file at `path`, returning its contents if it was present
+    pub fn remove(&mut self, path: &str) -> Option<Vec<u8>> {
+        self.files.remove(path)
+    }
+
+    /// The contents of the file at `path`, if any
+    pub fn get(&self, path: &str) -> Option<&[u8]> {
+        self.files.get(path).map(Vec::as_slice)
+    }
+
+    /// Iterate over the paths and contents of every file, in path order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.files.iter().map(|(p, c)| (p.as_str(), c.as_slice()))
+    }
+
+    /// The number of files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether the tree has no files
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Write every file beneath the directory `dir`, creating directories as needed
+    pub fn write<P>(&self, dir: P) -> std::io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        for (relpath, contents) in self.iter() {
+            let path = dir.join(relpath);
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(path, contents)?;
+        }
+        Ok(())
+    }
+}
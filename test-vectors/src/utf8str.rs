@@ -1,10 +1,11 @@
+use crate::FromVector;
 use std::cmp::PartialEq;
 use std::ops::Deref;
 use std::str::Utf8Error;
 
 /// Convenience type for tests with string vectors
 ///
-/// A `Utf8Str<'a>` should behave identically to `&'a str` except it implements
+/// A `Utf8Str<'a>` should behave identically to `&'a str` except it implements [FromVector] and
 /// `TryFrom<&[u8]>` with UTF-8 decoding.  This provides a convenient short hand for
 /// [test_vectors](crate::test_vectors) tests which operate on strings, rather than bytes.
 ///
@@ -30,6 +31,12 @@ impl<'a> Deref for Utf8Str<'a> {
     }
 }
 
+impl<'a> AsRef<[u8]> for Utf8Str<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
 impl<'a> TryFrom<&'a [u8]> for Utf8Str<'a> {
     type Error = Utf8Error;
 
@@ -38,6 +45,14 @@ impl<'a> TryFrom<&'a [u8]> for Utf8Str<'a> {
     }
 }
 
+impl<'a> FromVector<'a> for Utf8Str<'a> {
+    type Error = Utf8Error;
+
+    fn from_vector(bytes: &'a [u8]) -> Result<Utf8Str<'a>, Utf8Error> {
+        Utf8Str::try_from(bytes)
+    }
+}
+
 impl<'a, Rhs> PartialEq<Rhs> for Utf8Str<'a>
 where
     str: PartialEq<Rhs>,
@@ -46,3 +61,76 @@ where
         self.0.eq(other)
     }
 }
+
+/// The owned counterpart of [Utf8Str], for tests which keep a string vector beyond its bytes
+///
+/// A `Utf8String` should behave identically to [String] except it implements `TryFrom<&[u8]>`
+/// with UTF-8 decoding, copying the bytes, as its [FromVector] impl does. This suits vectors
+/// loaded at runtime with [Case::load](crate::Case::load), where there is no `'static` data to
+/// borrow.
+///
+/// # Example
+///
+/// ```
+/// use test_vectors::Utf8String;
+///
+/// let input = std::fs::read("test-data/example2/single_case/input")?;
+/// let text = Utf8String::try_from(&input[..]).unwrap();
+/// drop(input);
+/// assert_eq!(&*text, "this is the input\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Utf8String(String);
+
+impl Utf8String {
+    /// Take ownership of the decoded string
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for Utf8String {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Utf8String {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl TryFrom<&[u8]> for Utf8String {
+    type Error = Utf8Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Utf8String, Utf8Error> {
+        std::str::from_utf8(bytes).map(|s| Utf8String(s.to_string()))
+    }
+}
+
+impl FromVector<'_> for Utf8String {
+    type Error = Utf8Error;
+
+    fn from_vector(bytes: &[u8]) -> Result<Utf8String, Utf8Error> {
+        Utf8String::try_from(bytes)
+    }
+}
+
+impl From<Utf8String> for String {
+    fn from(s: Utf8String) -> String {
+        s.0
+    }
+}
+
+impl<Rhs> PartialEq<Rhs> for Utf8String
+where
+    str: PartialEq<Rhs>,
+{
+    fn eq(&self, other: &Rhs) -> bool {
+        self.0.as_str().eq(other)
+    }
+}
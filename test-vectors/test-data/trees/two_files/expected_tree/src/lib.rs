@@ -0,0 +1 @@
+pub fn f() {}
\ No newline at end of file
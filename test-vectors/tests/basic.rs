@@ -0,0 +1,143 @@
+use std::cmp::PartialEq;
+use std::ops::Deref;
+use std::str::Utf8Error;
+use test_vectors::{test_vectors, FromVector};
+
+#[test_vectors(dir = "tests/basic")]
+fn replace_spaces_with_underscore(input: &[u8], expected: &[u8]) {
+    let instr = std::str::from_utf8(input).unwrap();
+    let expstr = std::str::from_utf8(expected).unwrap();
+    let output = instr.replace(' ', "_");
+    assert_eq!(expstr, &output);
+}
+
+#[test_vectors(dir = "tests/basic")]
+fn replace_spaces_with_underscore_result(input: &[u8], expected: &[u8]) -> Result<(), Utf8Error> {
+    let instr = std::str::from_utf8(input)?;
+    let expstr = std::str::from_utf8(expected)?;
+    let output = instr.replace(' ', "_");
+    assert_eq!(expstr, &output);
+    Ok(())
+}
+
+// Every entry of each case directory is an argument:
+#[test_vectors(dir = "tests/basic", strict = true)]
+fn strict_replace_spaces_with_underscore(input: &[u8], expected: &[u8]) {
+    let output = input
+        .iter()
+        .map(|&b| if b == b' ' { b'_' } else { b })
+        .collect::<Vec<u8>>();
+    assert_eq!(expected, output);
+}
+
+#[derive(Debug)]
+struct Utf8Str<'a>(&'a str);
+
+impl<'a> Deref for Utf8Str<'a> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl<'a> FromVector<'a> for Utf8Str<'a> {
+    type Error = Utf8Error;
+
+    fn from_vector(bytes: &'a [u8]) -> Result<Utf8Str<'a>, Utf8Error> {
+        std::str::from_utf8(bytes).map(Utf8Str)
+    }
+}
+
+impl<'a, Rhs> PartialEq<Rhs> for Utf8Str<'a>
+where
+    str: PartialEq<Rhs>,
+{
+    fn eq(&self, other: &Rhs) -> bool {
+        self.0.eq(other)
+    }
+}
+
+#[test_vectors(dir = "tests/basic")]
+fn utf8str_replace_spaces_with_underscore(input: Utf8Str<'static>, expected: Utf8Str<'static>) {
+    let output = input.replace(' ', "_");
+    assert_eq!(expected, output);
+}
+
+#[test_vectors(dir = "tests/basic", strict = true)]
+fn owned_replace_spaces_with_underscore(input: String, expected: Vec<u8>) {
+    let output = input.replace(' ', "_");
+    assert_eq!(expected, output.into_bytes());
+}
+
+// `String` converts through `FromVector` like any other type, so an alias works too:
+type Text = String;
+
+#[test_vectors(dir = "tests/basic")]
+fn aliased_replace_spaces_with_underscore(input: Text, expected: std::string::String) {
+    assert_eq!(expected, input.replace(' ', "_"));
+}
+
+// An elided lifetime rather than `'static` also accepts vectors loaded at runtime:
+fn check_replace_spaces(input: Utf8Str<'_>, expected: Utf8Str<'_>) {
+    let output = input.replace(' ', "_");
+    assert_eq!(expected, output);
+}
+
+#[test_vectors(dir = "tests/basic")]
+fn borrowed_replace_spaces_with_underscore(input: Utf8Str<'_>, expected: Utf8Str<'_>) {
+    check_replace_spaces(input, expected);
+}
+
+#[test]
+fn borrowed_replace_spaces_with_underscore_at_runtime() -> std::io::Result<()> {
+    for case in test_vectors::Corpus::open("tests/basic")?.cases() {
+        let input = case.file("input")?;
+        let expected = case.load::<test_vectors::Utf8String>("expected")?;
+        check_replace_spaces(
+            Utf8Str::from_vector(&input).unwrap(),
+            Utf8Str::from_vector(expected.as_bytes()).unwrap(),
+        );
+    }
+    Ok(())
+}
+
+trait Replacer {
+    fn replace(input: &str) -> String;
+}
+
+struct StrReplace;
+
+impl Replacer for StrReplace {
+    fn replace(input: &str) -> String {
+        input.replace(' ', "_")
+    }
+}
+
+struct CharMap;
+
+impl Replacer for CharMap {
+    fn replace(input: &str) -> String {
+        input
+            .chars()
+            .map(|c| if c == ' ' { '_' } else { c })
+            .collect()
+    }
+}
+
+#[test_vectors(dir = "tests/basic", types(StrReplace = StrReplace, CharMap = CharMap))]
+fn generic_replace_spaces<R: Replacer>(input: Utf8Str<'static>, expected: Utf8Str<'static>) {
+    let output = R::replace(&input);
+    assert_eq!(expected, output);
+}
+
+#[test_vectors(dir = "tests/basic", params(repeat = [1, 3], sep = ["and", "or"]))]
+fn repeat_replace_spaces(
+    input: Utf8Str<'static>,
+    repeat: usize,
+    sep: &str,
+    expected: Utf8Str<'static>,
+) {
+    let output = vec![input.replace(' ', "_"); repeat].join(sep);
+    assert_eq!(output, vec![&*expected; repeat].join(sep));
+}
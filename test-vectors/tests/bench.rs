@@ -0,0 +1,23 @@
+#![cfg(feature = "bench")]
+
+use std::time::Duration;
+use test_vectors::bench_vectors;
+
+#[bench_vectors(dir = "tests/basic")]
+fn bench_replace_spaces(input: &[u8], expected: &[u8]) {
+    let output: Vec<u8> = input
+        .iter()
+        .map(|&b| if b == b' ' { b'_' } else { b })
+        .collect();
+    assert_eq!(expected, &output[..]);
+}
+
+#[test]
+fn bench_group_runs_every_case() {
+    let mut c = criterion::Criterion::default()
+        .warm_up_time(Duration::from_millis(1))
+        .measurement_time(Duration::from_millis(10))
+        .sample_size(10);
+
+    bench_replace_spaces(&mut c);
+}
@@ -0,0 +1,7 @@
+//! Run the `cargo-test-vectors` binary against the `tests/cli` corpus
+#![cfg(feature = "cli")]
+
+use test_vectors::test_vectors;
+
+#[test_vectors(dir = "tests/cli", strict = true, bin = "cargo-test-vectors")]
+fn cargo_test_vectors() {}
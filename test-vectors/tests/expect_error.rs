@@ -0,0 +1,40 @@
+use std::num::ParseIntError;
+use test_vectors::{test_vectors, FromVector, Utf8Str};
+
+// `bad_digit` and `not_utf8` contain an `expect_error` file:
+#[test_vectors(dir = "tests/numbers", strict = true, manifest = "SHA256SUMS")]
+fn parse_number(input: Utf8Str<'static>, expected: &[u8]) -> Result<(), ParseIntError> {
+    let n: u32 = input.parse()?;
+    assert_eq!(expected, n.to_string().as_bytes());
+    Ok(())
+}
+
+// Invalid UTF-8 fails the conversion of an owned `String` in the same way:
+#[test_vectors(dir = "tests/numbers", manifest = "SHA256SUMS")]
+fn parse_owned_number(input: String, expected: &[u8]) -> Result<(), ParseIntError> {
+    let n: u32 = input.parse()?;
+    assert_eq!(expected, n.to_string().as_bytes());
+    Ok(())
+}
+
+#[test_vectors(dir = "tests/invalid", expect_error = true)]
+fn parse_invalid_number(input: Utf8Str<'static>) -> Result<u32, ParseIntError> {
+    input.parse()
+}
+
+struct Number(u32);
+
+impl FromVector<'_> for Number {
+    type Error = String;
+
+    fn from_vector(bytes: &[u8]) -> Result<Number, String> {
+        let s = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+        s.parse().map(Number).map_err(|e| format!("{:?}: {}", s, e))
+    }
+}
+
+// A criterion returning `()` can only fail through conversion:
+#[test_vectors(dir = "tests/invalid", expect_error = true)]
+fn convert_invalid_number(input: Number) {
+    assert!(input.0 > 0);
+}
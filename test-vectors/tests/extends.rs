@@ -0,0 +1,28 @@
+use test_vectors::test_vectors;
+
+// `formal` inherits `name` from `base`, and `formal_sir` inherits `greeting` through `formal`:
+#[test_vectors(dir = "tests/overlay", strict = true)]
+fn inherited_files(greeting: &[u8], name: &[u8], expected: &[u8]) {
+    let output = [greeting, b", ", name].concat();
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn runtime_corpus_inherits_files() -> std::io::Result<()> {
+    let corpus = test_vectors::Corpus::open("tests/overlay")?;
+    let names: Vec<(&str, Vec<u8>)> = corpus
+        .cases()
+        .iter()
+        .map(|case| Ok((case.name(), case.file("name")?)))
+        .collect::<std::io::Result<_>>()?;
+
+    assert_eq!(
+        names,
+        [
+            ("base", b"world".to_vec()),
+            ("formal", b"world".to_vec()),
+            ("formal_sir", b"sir".to_vec()),
+        ]
+    );
+    Ok(())
+}
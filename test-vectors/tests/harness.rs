@@ -0,0 +1,15 @@
+use std::str::Utf8Error;
+use test_vectors::Case;
+
+fn replace_spaces(case: &Case) -> Result<(), Utf8Error> {
+    let input = case.file("input").unwrap();
+    let underscores = case.file("underscores").unwrap();
+
+    let output = std::str::from_utf8(&input)?.replace(' ', "_");
+    assert_eq!(std::str::from_utf8(&underscores)?, output);
+    Ok(())
+}
+
+fn main() {
+    test_vectors::harness::run("test-data/example2", replace_spaces)
+}
@@ -0,0 +1,37 @@
+use test_vectors::{test_vectors, CaseInfo};
+
+#[test_vectors(dir = "tests/tagged", strict = true)]
+fn read_meta(input: &[u8]) {
+    let case = CaseInfo::current().unwrap();
+    let meta = case.meta();
+    assert!(!input.is_empty());
+
+    match case.name() {
+        "quick" => {
+            assert_eq!(meta.description(), Some("A short input"));
+            assert_eq!(meta.tags(), ["fast"]);
+            assert_eq!(meta.references(), ["RFC 9999 section 1"]);
+        }
+        "slow_one" => {
+            assert_eq!(meta.description(), None);
+            assert_eq!(meta.tags(), ["slow"]);
+        }
+        "plain" => {
+            assert_eq!(meta.description(), None);
+            assert!(meta.tags().is_empty());
+        }
+        other => panic!("unexpected case {}", other),
+    }
+}
+
+#[test_vectors(dir = "tests/tagged", tags = ["fast"])]
+fn only_fast(input: &[u8]) {
+    assert!(!input.is_empty());
+    assert!(CaseInfo::current().unwrap().meta().has_tag("fast"));
+}
+
+#[test_vectors(dir = "tests/tagged", tags = ["!slow"])]
+fn except_slow(input: &[u8]) {
+    assert!(!input.is_empty());
+    assert!(!CaseInfo::current().unwrap().meta().has_tag("slow"));
+}
@@ -0,0 +1,21 @@
+use test_vectors::test_vectors;
+
+// `alpha` uses `_shared/key`, `beta` overrides it, and both use the top level `suffix`:
+#[test_vectors(dir = "tests/shared", strict = true)]
+fn shared_fixtures(input: &[u8], key: &[u8], suffix: &[u8], expected: &[u8]) {
+    let output = [input, b"-", key, suffix].concat();
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn runtime_corpus_uses_shared_fixtures() -> std::io::Result<()> {
+    let corpus = test_vectors::Corpus::open("tests/shared")?;
+    let keys: Vec<(&str, Vec<u8>)> = corpus
+        .cases()
+        .iter()
+        .map(|case| Ok((case.name(), case.file("key")?)))
+        .collect::<std::io::Result<_>>()?;
+
+    assert_eq!(keys, [("alpha", b"k".to_vec()), ("beta", b"K".to_vec())]);
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use test_vectors::{assert_tree_eq, test_vectors, Tree, Utf8Str};
+
+/// Unpack `path=contents` lines into a tree
+fn unpack(input: &str) -> Tree {
+    let mut tree = Tree::new();
+    for line in input.lines() {
+        let (path, contents) = line.split_once('=').unwrap();
+        tree.insert(path, contents);
+    }
+    tree
+}
+
+// `reordered` inherits the `expected_tree` directory from `flat`:
+#[test_vectors(dir = "tests/trees", strict = true)]
+fn unpack_tree(input: Utf8Str<'static>, expected_tree: Tree) {
+    assert_tree_eq!(expected_tree, unpack(&input));
+}
+
+// A `Tree` argument is recognized by the last segment of its type path:
+#[test_vectors(dir = "tests/trees")]
+fn count_files(input: Utf8Str<'static>, expected_tree: test_vectors::Tree) {
+    assert_eq!(expected_tree.len(), input.lines().count());
+}
+
+#[test]
+fn runtime_corpus_reads_trees() -> std::io::Result<()> {
+    let corpus = test_vectors::Corpus::open("tests/trees")?;
+    for case in corpus.cases() {
+        let input = String::from_utf8(case.file("input")?).unwrap();
+        assert_eq!(
+            case.tree("expected_tree")?,
+            unpack(&input),
+            "{}",
+            case.name()
+        );
+    }
+    Ok(())
+}
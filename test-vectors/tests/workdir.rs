@@ -0,0 +1,22 @@
+use test_vectors::{test_vectors, CaseTempDir, Env};
+
+// The criterion writes into its copy of the case directory, at a path given by the `env` file:
+#[test_vectors(dir = "tests/workdir", strict = true)]
+fn write_in_workdir(workdir: CaseTempDir, env: Env, expected: &[u8]) -> std::io::Result<()> {
+    let output = workdir.join(env.get("OUTPUT").unwrap());
+    std::fs::create_dir_all(output.parent().unwrap())?;
+    std::fs::copy(workdir.join("input"), &output)?;
+
+    assert_eq!(expected, std::fs::read(&output)?);
+    assert!(workdir.join("env").is_file());
+    Ok(())
+}
+
+#[test]
+fn workdir_is_removed_after_success() {
+    let path = CaseTempDir::new("tests/workdir/copy")
+        .unwrap()
+        .to_path_buf();
+    assert!(!path.exists());
+    assert!(!std::path::Path::new("tests/workdir/copy/copy.txt").exists());
+}
@@ -0,0 +1,32 @@
+//! Save mismatched actual values beneath [test_vectors::ACTUAL_DIR_VAR]
+//!
+//! This binary holds the only tests which set the variable, and it is set once before any case
+//! reads it, so it cannot race with other tests.
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use test_vectors::{test_vectors, ACTUAL_DIR_VAR};
+
+fn actual_dir() -> &'static PathBuf {
+    static DIR: OnceLock<PathBuf> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let dir = target_test_dir::get_base_test_dir().join("write_actual");
+        std::env::set_var(ACTUAL_DIR_VAR, &dir);
+        dir
+    })
+}
+
+#[test_vectors(dir = "tests/basic", write_actual = true)]
+fn write_actual_on_mismatch(expected: &[u8]) {
+    let actualdir = actual_dir();
+    let mismatch = std::panic::catch_unwind(|| test_vectors::assert_vector_eq!(expected, "wrong"));
+    assert!(mismatch.is_err());
+
+    // The actual directory takes precedence over writing beside the case file:
+    let case = test_vectors::CaseInfo::current().unwrap();
+    let path = actualdir
+        .join("tests/basic")
+        .join(case.name())
+        .join("expected.actual");
+    assert_eq!(std::fs::read(path).unwrap(), b"wrong");
+    assert!(!case.dir().unwrap().join("expected.actual").exists());
+}